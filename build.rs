@@ -0,0 +1,39 @@
+// Build script: records build details reported by `--build-info`.
+
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let lock_path = Path::new(&manifest_dir).join("Cargo.lock");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Resolve the exact ergo-lib version from the lockfile, falling back to "unknown"
+    let ergo_lib_version = fs::read_to_string(&lock_path)
+        .ok()
+        .and_then(|lock| locked_version(&lock, "ergo-lib"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ERGO_LIB_VERSION={}", ergo_lib_version);
+
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=BUILD_TARGET={}", target);
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=BUILD_PROFILE={}", profile);
+}
+
+/// Finds the version of `name` in a Cargo.lock file
+fn locked_version(lock: &str, name: &str) -> Option<String> {
+    let needle = format!("name = \"{}\"", name);
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == needle {
+            let version = lines.next()?.trim();
+            return version
+                .strip_prefix("version = \"")
+                .and_then(|v| v.strip_suffix('"'))
+                .map(str::to_string);
+        }
+    }
+    None
+}
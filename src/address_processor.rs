@@ -1,73 +1,294 @@
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicUsize, AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use rayon::prelude::*;
-use crate::utils::{generate_addresses, generate_secure_mnemonic, SecureSeed};
-use crate::progress::{ProgressTracker, StatsSummary};
+use crate::utils::{
+    derive_addresses, generate_secure_mnemonic, generate_simulated_addresses, random_word_count, seed_entropy_hex,
+    derivation_path_string, verify_mnemonic, AddressFormat, AddressInfo, EntropySource, SecureSeed, SeedOutputFormat, WordMix,
+    ERGO_COIN_TYPE, SIMULATED_SEED, UNIFORM_WORD_MIX,
+};
+use crate::progress::{ProgressTracker, StatsSummary, Throttle};
 use crate::matcher::PatternMatcher;
+use crate::paper_wallet::render_qr_terminal;
 use crate::crypto;
+use crate::affinity;
+use zeroize::Zeroizing;
 
-// Result type: (mnemonic, address, matched pattern, address position, seed word count)
-pub type MatchResult = (String, String, String, u32, usize);
+/// Minimum time between progress callbacks
+const PROGRESS_CALLBACK_INTERVAL: Duration = Duration::from_millis(250);
+
+// Result type: (mnemonic, address, matched pattern, address position, seed word count,
+// addresses checked since the previous match of the same pattern)
+pub type MatchResult = (String, String, String, u32, usize, usize);
 
 // Secure version of the result type that zeroes memory when dropped
-type SecureMatchResult = (SecureSeed, String, String, u32, usize);
+type SecureMatchResult = (SecureSeed, String, String, u32, usize, usize);
+
+// A match produced inside a parallel batch, before it is accepted and counted.
+// The last field is the number of addresses into the batch at which it was found.
+type Candidate = (SecureSeed, String, String, u32, usize, usize);
+
+// Matches found so far per pattern, sorted by pattern
+pub type PatternTally = Vec<(String, usize)>;
+
+// A match from a seed list: (1-based line number, address, matched patterns, position)
+pub type SeedListMatch = (usize, String, String, u32);
+
+// Extra acceptance check on addresses that already matched a pattern
+type AcceptPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+// Balanced-mode candidate: carries every pattern the address matched so each one is credited.
+type MultiCandidate = (SecureSeed, String, Vec<String>, u32, usize, usize);
+
+/// Atomically claims one of the `num_results` result slots.
+/// Returns the 1-based match number, or `None` once the cap has been reached.
+fn claim_result_slot(found_count: &AtomicUsize, num_results: usize) -> Option<usize> {
+    found_count
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+            if n < num_results { Some(n + 1) } else { None }
+        })
+        .ok()
+        .map(|prev| prev + 1)
+}
 
-/// Address processor for finding vanity addresses
+/// Addresses checked since the previous match of `pattern` (or since the search started),
+/// given the absolute address count `at` where the new match was found.
+fn addresses_since_last_match(last_match_at: &mut HashMap<String, usize>, pattern: &str, at: usize) -> usize {
+    let previous = last_match_at.insert(pattern.to_string(), at).unwrap_or(0);
+    at.saturating_sub(previous)
+}
+
+/// Keeps at most `keep` candidates, preferring the lowest address index (earliest seed on
+/// ties), and returns them in their original order so match counters stay in sequence.
+fn keep_lowest_indices<T>(chunk: Vec<T>, keep: usize, position: impl Fn(&T) -> u32) -> Vec<T> {
+    if chunk.len() <= keep {
+        return chunk;
+    }
+    let mut indexed: Vec<(usize, T)> = chunk.into_iter().enumerate().collect();
+    indexed.sort_by_key(|(i, candidate)| (position(candidate), *i));
+    indexed.truncate(keep);
+    indexed.sort_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// Formats the non-secret fields of a match as a single `key=value` line.
+/// Multi-pattern labels are joined without spaces so every field stays one token.
+fn compact_match_fields(total_found: usize, pattern: &str, address: &str, position: u32, wc: usize, checked: usize) -> String {
+    format!(
+        "MATCH n={} pattern={} addr={} pos={} words={} checked={}",
+        total_found, pattern.replace(", ", ","), address, position, wc, checked
+    )
+}
+
+/// Search generations used for cancellation. A search runs in the generation that is current
+/// when it starts; `cancel` ends every generation up to the current one and `advance` starts a
+/// fresh one. Nothing is ever un-cancelled, so unlike a shared flag that is set and cleared
+/// again, a batch still running from an old search can't miss its cancellation.
+struct SearchEpochs {
+    current: AtomicUsize,
+    cancelled_through: AtomicUsize,
+}
+
+impl SearchEpochs {
+    fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(1),
+            cancelled_through: AtomicUsize::new(0),
+        }
+    }
+
+    fn current(&self) -> usize {
+        self.current.load(Ordering::SeqCst)
+    }
+
+    /// Cancels the current generation and every earlier one
+    fn cancel(&self) {
+        self.cancelled_through.fetch_max(self.current(), Ordering::SeqCst);
+    }
+
+    /// Cancels everything running and starts a new generation for the next search
+    fn advance(&self) {
+        self.cancel();
+        self.current.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn is_cancelled(&self, epoch: usize) -> bool {
+        epoch <= self.cancelled_through.load(Ordering::Relaxed)
+    }
+}
+
+/// Address processor for finding vanity addresses.
+/// All search, cancellation and throttling state is per instance, so several processors
+/// can search concurrently (each on its own pool) and be cancelled independently.
 pub struct AddressProcessor {
     progress: ProgressTracker,
-    max_batch_size: usize,
-    min_batch_size: usize,
+    // Worker pool owned by this processor (not the Rayon global pool)
+    thread_pool: rayon::ThreadPool,
+    // Cores the workers were asked to run on, and how many workers the OS actually pinned
+    affinity: Option<(Vec<usize>, Arc<AtomicUsize>)>,
+    max_batch_size: AtomicUsize,
+    min_batch_size: AtomicUsize,
     batch_adjust_interval: usize,
     batch_size: Arc<AtomicUsize>,
     batch_counter: Arc<AtomicUsize>,
-    performance_metrics: Arc<Mutex<HashMap<usize, Duration>>>,
-    should_cancel: Arc<AtomicBool>,
+    // Starting batch size chosen by the caller (e.g. from auto-tuning), and whether it is
+    // pinned so adaptive adjustment is skipped
+    initial_batch_size: Mutex<Option<usize>>,
+    batch_size_pinned: AtomicBool,
+    // Duration of the most recent batch, which adaptive sizing steers toward its target.
+    // A single slot rather than a per-thread map, so long runs hold constant memory
+    last_batch_duration: Mutex<Option<Duration>>,
+    epochs: SearchEpochs,
+    // Matches recorded so far in the current search: per pattern, and in total
+    pattern_tally: Arc<Mutex<HashMap<String, usize>>>,
+    found_total: Arc<AtomicUsize>,
     // Optional callback for real‐time result reporting
-    result_callback: Arc<Mutex<Option<Box<dyn Fn(&str, &str, &str, u32, usize) + Send + Sync>>>>,
+    result_callback: Arc<Mutex<Option<Box<dyn Fn(&str, &str, &str, u32, usize, usize) + Send + Sync>>>>,
+    // Custom criterion an address must also satisfy to be accepted
+    accept_predicate: Mutex<Option<AcceptPredicate>>,
+    // How seeds are printed in console match logs
+    seed_format: Mutex<SeedOutputFormat>,
+    // Print seeds only as a terminal QR code, never as text
+    qr_only_seed: AtomicBool,
+    // Print each match as one key=value line instead of a block
+    compact: AtomicBool,
+    // Replace seeds with <masked> in console match output
+    mask_seed: AtomicBool,
+    // No console match output or progress bar, for front ends that draw their own
+    quiet: AtomicBool,
+    // Match against random Base58 strings instead of derived addresses
+    simulate: AtomicBool,
+    // Panic in the first simulated candidate, to exercise error recovery (see set_simulate)
+    simulate_failure: AtomicBool,
+    // Treat num_results as a soft target and search until cancelled or timed out
+    keep_going: AtomicBool,
+    // When a batch has more matches than still needed, take the lowest address indices first
+    prefer_low_index: AtomicBool,
+    // Draw seed entropy from the OS RNG instead of the thread-local CSPRNG
+    os_entropy: AtomicBool,
+    // Weights for 12, 15 and 24-word seeds when searching all lengths (word count 0)
+    word_mix: [AtomicU32; 3],
+    // BIP44 coin type addresses are derived under (`ERGO_COIN_TYPE` unless overridden)
+    coin_type: AtomicU32,
+    // Keep at most one match per seed and stop deriving a seed's addresses once one matches
+    one_per_seed: AtomicBool,
+    // Report every matching address of a seed in plain searches, not just the first
+    all_per_seed: AtomicBool,
+    // Hard cap on matches per search, even with keep-going (usize::MAX when unset)
+    max_results: AtomicUsize,
+    // Optional time limit for a search, and the deadline derived from it when a search starts
+    timeout: Mutex<Option<Duration>>,
+    deadline: Mutex<Option<Instant>>,
+    // Switch to 12-word seeds if nothing matched this long after the start, the time at which
+    // that happens for the current search, and whether it has happened
+    word_count_fallback: Mutex<Option<Duration>>,
+    fallback_at: Mutex<Option<Instant>>,
+    fallback_engaged: AtomicBool,
+    // Closest non-matching candidate seen so far, tracked when nearest-match fallback is on
+    track_nearest: AtomicBool,
+    best_distance: AtomicUsize,
+    nearest_match: Mutex<Option<(SecureMatchResult, usize)>>,
     // Crypto acceleration context
     accel_ctx: &'static crypto::AccelContext,
 }
 
+impl Default for AddressProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AddressProcessor {
+    /// Creates a processor using one worker thread per logical CPU.
     pub fn new() -> Self {
-        // Determine thread count
-        let cpu_count = num_cpus::get();
-        let thread_count = cpu_count.max(1);
+        Self::with_threads(num_cpus::get())
+    }
+
+    /// Creates a processor using one worker thread per logical CPU that never draws the
+    /// console progress bar. Progress callbacks still fire.
+    pub fn without_progress_bar() -> Self {
+        Self::build(num_cpus::get(), false, None)
+    }
+
+    /// Creates a processor with its own pool of `thread_count` worker threads.
+    /// Each processor owns its pool, so a new processor can use a different thread count.
+    pub fn with_threads(thread_count: usize) -> Self {
+        Self::build(thread_count, true, None)
+    }
+
+    /// Creates a processor with one worker thread per core in `cores`, each pinned to its
+    /// core (see `affinity`). Check the cores with `affinity::check_cores` first.
+    pub fn pinned(cores: Vec<usize>, show_progress_bar: bool) -> Self {
+        Self::build(cores.len(), show_progress_bar, Some(cores))
+    }
+
+    fn build(thread_count: usize, show_progress_bar: bool, cores: Option<Vec<usize>>) -> Self {
+        let thread_count = thread_count.max(1);
         
         // Get hardware acceleration context
         let accel_ctx = crypto::get_context();
         
-        // Configure the Rayon global thread pool once
-        static THREAD_POOL_INITIALIZED: AtomicBool = AtomicBool::new(false);
-        if !THREAD_POOL_INITIALIZED.load(Ordering::SeqCst) {
-            if let Err(e) = rayon::ThreadPoolBuilder::new()
-                .num_threads(thread_count)
-                .build_global() 
-            {
-                eprintln!("Warning: Failed to configure Rayon thread pool: {}", e);
-            } else {
-                THREAD_POOL_INITIALIZED.store(true, Ordering::SeqCst);
-            }
+        // Build a Rayon pool local to this processor, pinning its workers if asked to
+        let affinity = cores.filter(|cores| !cores.is_empty()).map(|cores| (cores, Arc::new(AtomicUsize::new(0))));
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .thread_name(|i| format!("vanity-worker-{}", i));
+        if let Some((cores, pinned)) = &affinity {
+            let (cores, pinned) = (cores.clone(), Arc::clone(pinned));
+            builder = builder.start_handler(move |i| affinity::pin_worker(&cores, i, &pinned));
         }
+        let thread_pool = builder.build().expect("Failed to build worker thread pool");
 
         // Use optimized batch sizes based on hardware capabilities
         let initial_batch_size = accel_ctx.get_optimal_batch_count();
-        let min_batch_size = accel_ctx.get_optimal_batch_size() * 10;
-        let max_batch_size = initial_batch_size * 3;
+        let (min_batch_size, max_batch_size) = Self::default_batch_size_bounds();
         let batch_adjust_interval = 10;
 
+        let progress = ProgressTracker::new(thread_count, show_progress_bar);
+        // The progress bar reads the found count straight from the tracker
+        let found_total = Arc::clone(&progress.matches_found);
+
         Self {
-            progress: ProgressTracker::new(thread_count, true),
-            max_batch_size,
-            min_batch_size,
+            progress,
+            thread_pool,
+            affinity,
+            max_batch_size: AtomicUsize::new(max_batch_size),
+            min_batch_size: AtomicUsize::new(min_batch_size),
             batch_adjust_interval,
             batch_size: Arc::new(AtomicUsize::new(initial_batch_size)),
             batch_counter: Arc::new(AtomicUsize::new(0)),
-            performance_metrics: Arc::new(Mutex::new(HashMap::new())),
-            should_cancel: Arc::new(AtomicBool::new(false)),
+            initial_batch_size: Mutex::new(None),
+            batch_size_pinned: AtomicBool::new(false),
+            last_batch_duration: Mutex::new(None),
+            epochs: SearchEpochs::new(),
+            pattern_tally: Arc::new(Mutex::new(HashMap::new())),
+            found_total,
             result_callback: Arc::new(Mutex::new(None)),
+            accept_predicate: Mutex::new(None),
+            seed_format: Mutex::new(SeedOutputFormat::Words),
+            qr_only_seed: AtomicBool::new(false),
+            compact: AtomicBool::new(false),
+            mask_seed: AtomicBool::new(false),
+            quiet: AtomicBool::new(false),
+            simulate: AtomicBool::new(false),
+            simulate_failure: AtomicBool::new(false),
+            keep_going: AtomicBool::new(false),
+            prefer_low_index: AtomicBool::new(false),
+            os_entropy: AtomicBool::new(false),
+            word_mix: UNIFORM_WORD_MIX.map(AtomicU32::new),
+            coin_type: AtomicU32::new(ERGO_COIN_TYPE),
+            one_per_seed: AtomicBool::new(false),
+            all_per_seed: AtomicBool::new(false),
+            max_results: AtomicUsize::new(usize::MAX),
+            timeout: Mutex::new(None),
+            deadline: Mutex::new(None),
+            word_count_fallback: Mutex::new(None),
+            fallback_at: Mutex::new(None),
+            fallback_engaged: AtomicBool::new(false),
+            track_nearest: AtomicBool::new(false),
+            best_distance: AtomicUsize::new(usize::MAX),
+            nearest_match: Mutex::new(None),
             accel_ctx,
         }
     }
@@ -77,16 +298,10 @@ impl AddressProcessor {
     where
         F: Fn(usize, usize, f64, f64) + Send + Sync + 'static,
     {
+        // Only call back every 250ms to avoid spamming
+        let throttle = Throttle::new(PROGRESS_CALLBACK_INTERVAL);
         let throttled_callback = move |seeds, addresses, seed_rate, addr_rate| {
-            // Only call back every 250ms to avoid spamming
-            static LAST_UPDATE: AtomicUsize = AtomicUsize::new(0);
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as usize;
-            
-            if now.saturating_sub(LAST_UPDATE.load(Ordering::Relaxed)) > 250 {
-                LAST_UPDATE.store(now, Ordering::Relaxed);
+            if throttle.ready() {
                 callback(seeds, addresses, seed_rate, addr_rate);
             }
         };
@@ -94,14 +309,214 @@ impl AddressProcessor {
         self.progress.set_callback(throttled_callback);
     }
 
-    /// Optional callback to handle *each* matching result in real time
+    /// Like `set_progress_callback`, but also reports the number of matches found so far and a
+    /// per-pattern tally: (seeds, addresses, seed rate, address rate, found, tally).
+    /// Replaces any callback set with `set_progress_callback`.
+    pub fn set_detailed_progress_callback<F>(&self, callback: F)
+    where
+        F: Fn(usize, usize, f64, f64, usize, &[(String, usize)]) + Send + Sync + 'static,
+    {
+        let tally = Arc::clone(&self.pattern_tally);
+        let found_total = Arc::clone(&self.found_total);
+        let throttle = Throttle::new(PROGRESS_CALLBACK_INTERVAL);
+        let detailed_callback = move |seeds, addresses, seed_rate, addr_rate| {
+            if throttle.ready() {
+                let mut breakdown: PatternTally = tally
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|(pattern, count)| (pattern.clone(), *count))
+                    .collect();
+                breakdown.sort();
+                callback(seeds, addresses, seed_rate, addr_rate, found_total.load(Ordering::Relaxed), &breakdown);
+            }
+        };
+
+        self.progress.set_callback(detailed_callback);
+    }
+
+    /// Optional callback to handle *each* matching result in real time.
+    /// Arguments: (mnemonic, address, pattern, position, word count, addresses checked)
     pub fn set_result_callback<F>(&self, callback: F)
     where
-        F: Fn(&str, &str, &str, u32, usize) + Send + Sync + 'static,
+        F: Fn(&str, &str, &str, u32, usize, usize) + Send + Sync + 'static,
     {
         *self.result_callback.lock().unwrap() = Some(Box::new(callback));
     }
 
+    /// Require addresses to also satisfy `predicate`: a candidate is accepted only if it
+    /// matches a pattern AND the predicate returns true. The predicate is called from the
+    /// worker threads concurrently, so it must be thread-safe and should be fast and free
+    /// of blocking. It applies from the next search on.
+    pub fn set_accept_predicate<F>(&self, predicate: F)
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        *self.accept_predicate.lock().unwrap() = Some(Arc::new(predicate));
+    }
+
+    /// The (min, max) range the adaptive batch sizing stays within, derived from CPU features
+    pub fn default_batch_size_bounds() -> (usize, usize) {
+        let accel_ctx = crypto::get_context();
+        (accel_ctx.get_optimal_batch_size() * 10, accel_ctx.get_optimal_batch_count() * 3)
+    }
+
+    /// Override the (min, max) range the adaptive batch sizing stays within
+    pub fn set_batch_size_bounds(&self, min: usize, max: usize) {
+        self.min_batch_size.store(min.max(1), Ordering::SeqCst);
+        self.max_batch_size.store(max.max(min).max(1), Ordering::SeqCst);
+    }
+
+    /// The current (min, max) range of the adaptive batch sizing
+    pub fn batch_size_bounds(&self) -> (usize, usize) {
+        (self.min_batch_size.load(Ordering::Relaxed), self.max_batch_size.load(Ordering::Relaxed))
+    }
+
+    /// Start searches at `size` seeds per batch instead of the CPU-derived default;
+    /// adaptive sizing still adjusts it within the bounds
+    pub fn set_initial_batch_size(&self, size: usize) {
+        let (min, max) = self.batch_size_bounds();
+        *self.initial_batch_size.lock().unwrap() = Some(size.clamp(min, max));
+        self.batch_size_pinned.store(false, Ordering::SeqCst);
+    }
+
+    /// Use exactly `size` seeds per batch and disable adaptive adjustment
+    pub fn pin_batch_size(&self, size: usize) {
+        *self.initial_batch_size.lock().unwrap() = Some(size.max(1));
+        self.batch_size_pinned.store(true, Ordering::SeqCst);
+    }
+
+    /// Choose how seeds are printed in console match logs (words by default)
+    pub fn set_seed_output_format(&self, format: SeedOutputFormat) {
+        *self.seed_format.lock().unwrap() = format;
+    }
+
+    /// Print seeds in console match logs only as a QR code; overrides the seed output format
+    pub fn set_qr_only_seed(&self, qr_only: bool) {
+        self.qr_only_seed.store(qr_only, Ordering::SeqCst);
+    }
+
+    /// Print every match as a single `key=value` line (for grep and log shippers)
+    pub fn set_compact(&self, compact: bool) {
+        self.compact.store(compact, Ordering::SeqCst);
+    }
+
+    /// Replace the seed with `<masked>` in console match output
+    pub fn set_mask_seed(&self, mask: bool) {
+        self.mask_seed.store(mask, Ordering::SeqCst);
+    }
+
+    /// Suppress console match output and the progress bar, for front ends that own the terminal
+    pub fn set_quiet(&self, quiet: bool) {
+        self.quiet.store(quiet, Ordering::SeqCst);
+        self.progress.set_progress_bar_visible(!quiet);
+    }
+
+    /// Prints results in the console match format, numbered from 1
+    pub fn print_results(&self, results: &[MatchResult]) {
+        for (i, (mnemonic, address, pattern, position, wc, checked)) in results.iter().enumerate() {
+            self.print_match(i + 1, mnemonic, address, pattern, *position, *wc, *checked);
+        }
+    }
+
+    /// Enable simulation mode: candidates are random Base58 strings, not derived addresses,
+    /// and results carry a placeholder seed. For testing and demos only.
+    /// With `VANITYGEN_SIMULATE_FAILURE` set, a simulated search panics in a worker thread
+    /// so error handling can be tested.
+    pub fn set_simulate(&self, simulate: bool) {
+        self.simulate.store(simulate, Ordering::SeqCst);
+        let failure = simulate && std::env::var_os("VANITYGEN_SIMULATE_FAILURE").is_some();
+        self.simulate_failure.store(failure, Ordering::SeqCst);
+    }
+
+    /// Keep searching after `num_results` matches until cancelled or timed out.
+    /// Every match is still streamed to the callback and console, but only the first
+    /// `num_results` are kept in memory and returned.
+    pub fn set_keep_going(&self, keep_going: bool) {
+        self.keep_going.store(keep_going, Ordering::SeqCst);
+    }
+
+    /// Stop every search after `max_results` matches, whatever the requested number or
+    /// keep-going says. Bounds the memory front ends spend collecting streamed matches.
+    pub fn set_max_results(&self, max_results: Option<usize>) {
+        self.max_results.store(max_results.unwrap_or(usize::MAX), Ordering::SeqCst);
+    }
+
+    /// Prefer matches at low address indices, which wallets are more likely to use.
+    /// Each seed already reports its lowest matching index; with this set, a batch that
+    /// finds more matches than are still needed keeps the lowest indices instead of the
+    /// earliest seeds.
+    pub fn set_prefer_low_index(&self, prefer: bool) {
+        self.prefer_low_index.store(prefer, Ordering::SeqCst);
+    }
+
+    /// Choose where seed entropy comes from (the thread-local CSPRNG by default)
+    pub fn set_entropy_source(&self, source: EntropySource) {
+        self.os_entropy.store(source == EntropySource::Os, Ordering::SeqCst);
+    }
+
+    /// Weights for picking 12, 15 and 24-word seeds in searches over all lengths (uniform by
+    /// default). Trades speed (shorter seeds) against security (longer seeds).
+    pub fn set_word_mix(&self, mix: WordMix) {
+        for (slot, weight) in self.word_mix.iter().zip(mix) {
+            slot.store(weight, Ordering::SeqCst);
+        }
+    }
+
+    /// Derive addresses under m/44'/`coin_type`'/0'/0/idx instead of Ergo's 429'. Standard Ergo
+    /// wallets won't derive addresses found under any other coin type.
+    pub fn set_coin_type(&self, coin_type: u32) {
+        self.coin_type.store(coin_type, Ordering::SeqCst);
+    }
+
+    /// Record only the first match of each seed, for more seed diversity. A seed's remaining
+    /// addresses aren't derived once one matches. Without balanced matching a seed already
+    /// yields a single match; this also applies it to balanced searches and seed lists.
+    pub fn set_one_per_seed(&self, one_per_seed: bool) {
+        self.one_per_seed.store(one_per_seed, Ordering::SeqCst);
+    }
+
+    /// Report every matching address of a seed in plain (non-balanced) searches, instead of
+    /// stopping at the first. Each counts as a result. Off by default, since deriving every
+    /// address of a seed that already matched is extra work.
+    pub fn set_all_per_seed(&self, all_per_seed: bool) {
+        self.all_per_seed.store(all_per_seed, Ordering::SeqCst);
+    }
+
+    /// Show progress against this many expected addresses in the progress bar (see
+    /// `estimator::expected_search_addresses`); `None` hides it
+    pub fn set_expected_addresses(&self, expected: Option<f64>) {
+        self.progress.set_expected_addresses(expected);
+    }
+
+    /// Stop each search after `timeout` has elapsed (no limit by default)
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        *self.timeout.lock().unwrap() = timeout;
+    }
+
+    /// Generate 12-word seeds, which are quicker to derive, once a search has gone `after`
+    /// without a match (off by default). Matches still report their actual word count.
+    pub fn set_word_count_fallback(&self, after: Option<Duration>) {
+        *self.word_count_fallback.lock().unwrap() = after;
+    }
+
+    /// Track the closest near miss (by edit distance) so it can be reported when a
+    /// search ends without an exact match. See `take_nearest_match`.
+    pub fn set_track_nearest(&self, track: bool) {
+        self.track_nearest.store(track, Ordering::SeqCst);
+    }
+
+    /// Takes the closest non-matching candidate seen by the last search, with its edit distance.
+    pub fn take_nearest_match(&self) -> Option<(MatchResult, usize)> {
+        self.nearest_match
+            .lock()
+            .unwrap()
+            .take()
+            .map(|((seed, address, pattern, position, wc, checked), distance)| {
+                ((seed.expose(), address, pattern, position, wc, checked), distance)
+            })
+    }
+
     /// Public entry point to find addresses matching patterns
     pub fn find_matches(
         &self,
@@ -111,80 +526,297 @@ impl AddressProcessor {
         balanced: bool,
         addresses_per_seed: u32,
     ) -> Vec<MatchResult> {
-        // Adjust the initial batch size if needed based on word count
+        // Adjust the initial batch size if needed based on word count, unless one was chosen
         let optimal_batch_size = self.accel_ctx.get_optimal_batch_count();
-        let initial_batch_size = if word_count == 0 {
-            optimal_batch_size // Mixed word count, use default
-        } else if word_count == 12 {
-            (optimal_batch_size * 12) / 10 // 20% higher for 12-word
-        } else if word_count == 15 {
-            optimal_batch_size
+        let initial_batch_size = if let Some(size) = *self.initial_batch_size.lock().unwrap() {
+            size
         } else {
-            (optimal_batch_size * 8) / 10 // 20% smaller for 24-word
+            let size = if word_count == 0 {
+                optimal_batch_size // Mixed word count, use default
+            } else if word_count == 12 {
+                (optimal_batch_size * 12) / 10 // 20% higher for 12-word
+            } else if word_count == 15 {
+                optimal_batch_size
+            } else {
+                (optimal_batch_size * 8) / 10 // 20% smaller for 24-word
+            };
+            let (min, max) = self.batch_size_bounds();
+            size.clamp(min, max)
         };
         self.batch_size.store(initial_batch_size, Ordering::Relaxed);
 
+        // Arm the timeout and clear any near miss from a previous search
+        *self.deadline.lock().unwrap() = self.timeout.lock().unwrap().map(|t| Instant::now() + t);
+        *self.fallback_at.lock().unwrap() = self.word_count_fallback.lock().unwrap().map(|t| Instant::now() + t);
+        self.fallback_engaged.store(false, Ordering::SeqCst);
+        self.best_distance.store(usize::MAX, Ordering::SeqCst);
+        self.pattern_tally.lock().unwrap().clear();
+        self.found_total.store(0, Ordering::SeqCst);
+        *self.nearest_match.lock().unwrap() = None;
+
         // Start progress monitor in background
-        let progress_thread = self.progress.start_monitoring_thread();
+        self.progress.start_monitoring_thread();
 
-        // Either balanced or any
-        let matches = if balanced {
-            self.find_balanced_matches(&matcher, word_count, num_results, addresses_per_seed)
-        } else {
-            self.find_any_matches(&matcher, word_count, num_results, addresses_per_seed)
-        };
+        // Either balanced or any, run on this processor's own pool
+        let epoch = self.epochs.current();
+        let matches = self.thread_pool.install(|| {
+            if balanced {
+                self.find_balanced_matches(&matcher, word_count, num_results, addresses_per_seed, epoch)
+            } else {
+                self.find_any_matches(&matcher, word_count, num_results, addresses_per_seed, epoch)
+            }
+        });
 
-        // Stop progress, wait for thread
+        // Stop progress and join the monitoring thread
         self.progress.stop();
-        let _ = progress_thread.join();
 
         matches
     }
 
+    /// Matches found by the current (or last) search, including keep-going extras
+    pub fn matches_found(&self) -> usize {
+        self.found_total.load(Ordering::SeqCst)
+    }
+
+    /// Checks caller-supplied mnemonics instead of generating seeds: derives
+    /// `addresses_per_seed` addresses from each and matches them against every pattern.
+    /// Returns the matches by line number and the number of lines skipped as invalid
+    /// mnemonics (blank lines are ignored).
+    pub fn check_seed_list(&self, matcher: &PatternMatcher, seeds: &[SecureSeed], addresses_per_seed: u32) -> (Vec<SeedListMatch>, usize) {
+        let invalid = AtomicUsize::new(0);
+        let accept = self.accept_predicate.lock().unwrap().clone();
+        self.progress.start_monitoring_thread();
+        let matches: Vec<SeedListMatch> = self.thread_pool.install(|| {
+            seeds
+                .par_iter()
+                .enumerate()
+                .filter(|(_, seed)| !seed.as_str().trim().is_empty())
+                .flat_map_iter(|(i, seed)| {
+                    let phrase = Zeroizing::new(seed.as_str().split_whitespace().collect::<Vec<_>>().join(" "));
+                    if verify_mnemonic(&phrase).is_err() {
+                        invalid.fetch_add(1, Ordering::Relaxed);
+                        return Vec::new();
+                    }
+                    // Derived one at a time, so --one-per-seed skips the addresses after a match
+                    let per_seed = if self.one_per_seed.load(Ordering::Relaxed) { 1 } else { usize::MAX };
+                    let mut derived = 0;
+                    let found: Vec<SeedListMatch> = derive_addresses(&phrase, addresses_per_seed, matcher.format(), self.coin_type.load(Ordering::Relaxed))
+                        .inspect(|_| derived += 1)
+                        .filter_map(|info| {
+                            let patterns = matcher.matches_all(&info.address);
+                            let accepted = accept.as_ref().is_none_or(|f| f(&info.address));
+                            (!patterns.is_empty() && accepted)
+                                .then(|| (i + 1, info.address, patterns.join(", "), info.position))
+                        })
+                        .take(per_seed)
+                        .collect();
+                    self.progress.record_processed(1, derived);
+                    found
+                })
+                .collect()
+        });
+        self.progress.stop();
+        (matches, invalid.into_inner())
+    }
+
     /// Get final performance statistics
     pub fn get_stats(&self) -> StatsSummary {
         self.progress.get_stats()
     }
 
+    /// Cores the workers run on and how many of them the OS pinned, for a `pinned` processor
+    pub fn affinity(&self) -> Option<(&[usize], usize)> {
+        self.affinity.as_ref().map(|(cores, pinned)| (cores.as_slice(), pinned.load(Ordering::Relaxed)))
+    }
+
     /// Request cancellation
     pub fn cancel(&self) {
-        self.should_cancel.store(true, Ordering::SeqCst);
+        self.epochs.cancel();
         self.progress.stop();
         *self.result_callback.lock().unwrap() = None;
-        println!("Cancellation requested — stopping search.");
-        std::thread::sleep(std::time::Duration::from_millis(50));
+        log::info!("Cancellation requested — stopping search.");
     }
     
-    /// Reset the processor for a fresh search
+    /// Reset the processor for a fresh search. Any search still winding down stays cancelled
+    /// and can no longer report matches; the next search starts in a new generation.
     pub fn reset(&self) {
-        self.should_cancel.store(true, Ordering::SeqCst);
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        self.should_cancel.store(false, Ordering::SeqCst);
+        self.epochs.advance();
         self.batch_counter.store(0, Ordering::Relaxed);
-        self.performance_metrics.lock().unwrap().clear();
+        *self.last_batch_duration.lock().unwrap() = None;
         self.progress.reset();
         *self.result_callback.lock().unwrap() = None;
     }
     
-    /// Internal check for cancellation
-    fn is_cancelled(&self) -> bool {
-        self.should_cancel.load(Ordering::SeqCst)
+    /// Lock-free cancellation check for the parallel sections of the search in `epoch`; the
+    /// timeout is only checked between batches by `is_cancelled`, which takes a lock
+    fn stop_requested(&self, epoch: usize) -> bool {
+        self.epochs.is_cancelled(epoch)
+    }
+
+    /// Internal check for cancellation of the search in `epoch`, or an expired timeout
+    fn is_cancelled(&self, epoch: usize) -> bool {
+        self.epochs.is_cancelled(epoch)
+            || self.deadline.lock().unwrap().is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Passes a match to the user callback unless the search in `epoch` was cancelled. The check
+    /// happens under the callback lock, which `reset` and `cancel` take after cancelling, so a
+    /// superseded search can never report into the callback of the search that replaced it.
+    #[allow(clippy::too_many_arguments)]
+    fn report_match(&self, epoch: usize, mnemonic: &str, address: &str, pattern: &str, position: u32, wc: usize, checked: usize) {
+        let callback = self.result_callback.lock().unwrap();
+        if let Some(callback) = callback.as_ref().filter(|_| !self.epochs.is_cancelled(epoch)) {
+            callback(mnemonic, address, pattern, position, wc, checked);
+        }
+    }
+
+    /// Number of matches after which a search stops: `num_results`, or unbounded with
+    /// keep-going, but never more than the `set_max_results` cap
+    fn match_limit(&self, num_results: usize) -> usize {
+        let limit = if self.keep_going.load(Ordering::Relaxed) { usize::MAX } else { num_results };
+        limit.min(self.max_results.load(Ordering::Relaxed))
+    }
+
+    /// Whether every match is printed rather than only the first ten and every tenth after
+    fn streams_every_match(&self) -> bool {
+        self.compact.load(Ordering::Relaxed) || self.keep_going.load(Ordering::Relaxed)
+    }
+
+    /// Record `address` as the nearest miss if it is closer than anything seen so far
+    fn update_nearest(&self, matcher: &PatternMatcher, secure_seed: &SecureSeed, address: &str, position: u32, wc: usize) {
+        let Some((distance, pattern)) = matcher.nearest(address) else {
+            return;
+        };
+        if distance >= self.best_distance.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut best = self.nearest_match.lock().unwrap();
+        if distance < self.best_distance.load(Ordering::Relaxed) {
+            self.best_distance.store(distance, Ordering::Relaxed);
+            let checked = self.progress.total_addresses.load(Ordering::Relaxed);
+            *best = Some(((secure_seed.clone(), address.to_string(), pattern, position, wc, checked), distance));
+        }
     }
 
     /// Internal conversion from secure results to exposed results
     fn convert_secure_to_exposed(&self, secure_results: Vec<SecureMatchResult>) -> Vec<MatchResult> {
         secure_results
             .into_iter()
-            .map(|(secure_seed, address, pattern, position, word_count)| {
-                (secure_seed.expose(), address, pattern, position, word_count)
+            .map(|(secure_seed, address, pattern, position, word_count, checked)| {
+                (secure_seed.expose(), address, pattern, position, word_count, checked)
             })
             .collect()
     }
 
+    /// Generate one candidate seed and the addresses to check for it, derived as they are consumed.
+    /// In simulation mode no key is derived and the seed is a placeholder.
+    fn generate_candidate(&self, word_count: usize, addresses_per_seed: u32, format: AddressFormat) -> (SecureSeed, usize, Box<dyn Iterator<Item = AddressInfo>>) {
+        let word_count = if self.fallback_engaged.load(Ordering::Relaxed) {
+            12
+        } else if word_count == 0 {
+            random_word_count(&self.word_mix.each_ref().map(|w| w.load(Ordering::Relaxed)))
+        } else {
+            word_count
+        };
+        if self.simulate.load(Ordering::Relaxed) {
+            if self.simulate_failure.load(Ordering::Relaxed) {
+                panic!("Simulated worker failure (VANITYGEN_SIMULATE_FAILURE is set)");
+            }
+            return (SecureSeed::new(SIMULATED_SEED), word_count, Box::new(generate_simulated_addresses(addresses_per_seed, format).into_iter()));
+        }
+
+        let source = if self.os_entropy.load(Ordering::Relaxed) { EntropySource::Os } else { EntropySource::Csprng };
+        let (secure_seed, actual_wc) = generate_secure_mnemonic(word_count, source);
+        let addrs = derive_addresses(secure_seed.as_str(), addresses_per_seed, format, self.coin_type.load(Ordering::Relaxed));
+        (secure_seed, actual_wc, Box::new(addrs))
+    }
+
+    /// Engages the word count fallback (see `set_word_count_fallback`) once its time is up
+    /// with nothing found. Checked once per batch rather than per seed.
+    fn check_word_count_fallback(&self, word_count: usize) {
+        if word_count == 12 || self.fallback_engaged.load(Ordering::Relaxed) || self.found_total.load(Ordering::Relaxed) > 0 {
+            return;
+        }
+        let due = self.fallback_at.lock().unwrap().is_some_and(|at| Instant::now() >= at);
+        if due && !self.fallback_engaged.swap(true, Ordering::SeqCst) {
+            let after = self.word_count_fallback.lock().unwrap().unwrap_or_default();
+            log::warn!("No match after {}s: switching to 12-word seeds for the rest of the search", after.as_secs());
+        }
+    }
+
+    /// Print a found match to the console using the configured seed format
+    #[allow(clippy::too_many_arguments)]
+    fn print_match(&self, total_found: usize, mnemonic: &str, address: &str, pattern: &str, position: u32, wc: usize, checked: usize) {
+        if self.quiet.load(Ordering::Relaxed) {
+            return;
+        }
+        let format = *self.seed_format.lock().unwrap();
+        let masked = self.mask_seed.load(Ordering::Relaxed);
+        let coin_type = self.coin_type.load(Ordering::Relaxed);
+        if self.compact.load(Ordering::Relaxed) {
+            let mut line = compact_match_fields(total_found, pattern, address, position, wc, checked);
+            line.push_str(&format!(" path={}", derivation_path_string(coin_type, 0, position)));
+            if masked {
+                line.push_str(" seed=<masked>");
+            } else {
+                if format.shows_words() {
+                    line.push_str(&format!(" seed=\"{}\"", mnemonic));
+                }
+                if format.shows_hex() {
+                    match seed_entropy_hex(mnemonic) {
+                        Ok(hex) => line.push_str(&format!(" entropy={}", hex.as_str())),
+                        Err(e) => log::warn!("Could not recover seed entropy: {}", e),
+                    }
+                }
+            }
+            if self.simulate.load(Ordering::Relaxed) {
+                line.push_str(" simulated=true");
+            }
+            println!("{}", line);
+            return;
+        }
+        if self.simulate.load(Ordering::Relaxed) {
+            println!("[SIMULATED - NOT A REAL WALLET]");
+        }
+        println!("MATCH #{} found pattern: {}", total_found, pattern);
+        println!("Address: {}", address);
+        println!("Position: {}", position);
+        if coin_type == ERGO_COIN_TYPE {
+            println!("Path: {}", derivation_path_string(coin_type, 0, position));
+        } else {
+            println!("Path: {} (non-standard coin type)", derivation_path_string(coin_type, 0, position));
+        }
+        println!("Found after {} addresses", checked);
+        if self.qr_only_seed.load(Ordering::Relaxed) {
+            println!("Seed phrase ({}-word), QR only - the code contains the secret:", wc);
+            match render_qr_terminal(mnemonic) {
+                Ok(qr) => println!("{}", qr),
+                Err(e) => log::warn!("Could not render seed QR code: {}", e),
+            }
+            println!("---------------------------");
+            return;
+        }
+        if masked {
+            println!("Seed phrase ({}-word): <masked>", wc);
+        } else if format.shows_words() {
+            println!("Seed phrase ({}-word): {}", wc, mnemonic);
+        }
+        if !masked && format.shows_hex() {
+            match seed_entropy_hex(mnemonic) {
+                Ok(hex) => println!("Seed entropy (hex): {}", hex.as_str()),
+                Err(e) => log::warn!("Could not recover seed entropy: {}", e),
+            }
+        }
+        println!("---------------------------");
+    }
+
     /// Adapt the batch size based on performance measurements
-    fn adjust_batch_size(&self, thread_idx: usize) {
-        let metrics = self.performance_metrics.lock().unwrap();
-        if let Some(last_duration) = metrics.get(&thread_idx) {
+    fn adjust_batch_size(&self) {
+        if self.batch_size_pinned.load(Ordering::Relaxed) {
+            return;
+        }
+        let last_batch_duration = *self.last_batch_duration.lock().unwrap();
+        if let Some(last_duration) = last_batch_duration {
             let duration_ms = last_duration.as_millis() as f64;
             let current_batch_size = self.batch_size.load(Ordering::Relaxed);
             
@@ -196,12 +828,12 @@ impl AddressProcessor {
                 // Too fast, increase batch size by 10-25% to reduce overhead
                 let increase_factor = 1.0 + (0.25 * (TARGET_MS_MIN - duration_ms) / TARGET_MS_MIN);
                 let bigger_batch = (current_batch_size as f64 * increase_factor) as usize;
-                bigger_batch.min(self.max_batch_size) // Cap at maximum
+                bigger_batch.min(self.max_batch_size.load(Ordering::Relaxed)) // Cap at maximum
             } else if duration_ms > TARGET_MS_MAX {
                 // Too slow, decrease batch size by 10-25%
                 let decrease_factor = 1.0 - (0.25 * (duration_ms - TARGET_MS_MAX) / TARGET_MS_MAX);
                 let smaller_batch = (current_batch_size as f64 * decrease_factor) as usize;
-                smaller_batch.max(self.min_batch_size) // Floor at minimum
+                smaller_batch.max(self.min_batch_size.load(Ordering::Relaxed)) // Floor at minimum
             } else {
                 // In the sweet spot
                 current_batch_size
@@ -214,6 +846,10 @@ impl AddressProcessor {
             // Only update if significantly different (>5%)
             if (aligned_size as f64 / current_batch_size as f64).abs() > 1.05 || 
                (aligned_size as f64 / current_batch_size as f64).abs() < 0.95 {
+                log::debug!(
+                    "Batch took {:.0}ms, adjusting batch size {} -> {}",
+                    duration_ms, current_batch_size, aligned_size
+                );
                 self.batch_size.store(aligned_size, Ordering::Relaxed);
             }
         }
@@ -228,133 +864,167 @@ impl AddressProcessor {
         word_count: usize,
         num_results: usize,
         addresses_per_seed: u32,
+        epoch: usize,
     ) -> Vec<MatchResult> {
-        let pattern_matches = Arc::new(Mutex::new(HashMap::<String, usize>::new()));
+        let pattern_matches = Arc::clone(&self.pattern_tally);
+        let limit = self.match_limit(num_results);
+        // Each pattern gets an even share of the results; once a pattern's share is filled
+        // the matcher stops testing it, so later work goes only to unfilled patterns.
+        let quota = limit.div_ceil(matcher.pattern_count().max(1));
         let found_count = Arc::new(AtomicUsize::new(0));
-        let results = Arc::new(Mutex::new(Vec::<SecureMatchResult>::new()));
+        // Filled only in the single-threaded merge after each parallel batch
+        let mut results = Vec::<SecureMatchResult>::new();
+        let mut last_match_at = HashMap::<String, usize>::new();
+        let accept = self.accept_predicate.lock().unwrap().clone();
 
         // Keep generating in parallel "batches" until we have enough or are cancelled
-        while found_count.load(Ordering::SeqCst) < num_results && !self.is_cancelled() {
-            if self.is_cancelled() {
+        while found_count.load(Ordering::SeqCst) < limit && !self.is_cancelled(epoch) {
+            if self.is_cancelled(epoch) {
                 break;
             }
+            self.check_word_count_fallback(word_count);
             let batch_num = self.batch_counter.fetch_add(1, Ordering::Relaxed);
             let current_batch_size = self.batch_size.load(Ordering::Relaxed);
 
             // Adjust batch size periodically
             if batch_num % self.batch_adjust_interval == 0 {
-                self.adjust_batch_size(0);
+                self.adjust_batch_size();
             }
 
             let start_time = Instant::now();
 
+            // Counted as generated, since seeds are skipped once the search stops and a seed's
+            // remaining addresses aren't derived after --one-per-seed keeps a match
+            let seeds_generated = AtomicUsize::new(0);
+            let addresses_derived = AtomicUsize::new(0);
+
             // Generate seeds in parallel
-            let chunk: Vec<Vec<SecureMatchResult>> = 
+            let chunk: Vec<Vec<MultiCandidate>> = 
                 (0..current_batch_size)
                     .into_par_iter()
-                    .map(|_| {
-                        if self.is_cancelled()
-                            || found_count.load(Ordering::SeqCst) >= num_results
+                    .map(|seed_idx| {
+                        if self.stop_requested(epoch)
+                            || found_count.load(Ordering::SeqCst) >= limit
                         {
                             return Vec::new();
                         }
                         
                         // Generate one seed, produce addresses
-                        let (secure_seed, actual_wc) = generate_secure_mnemonic(word_count);
-                        let addrs = generate_addresses(secure_seed.as_str(), addresses_per_seed);
+                        let (secure_seed, actual_wc, addrs) = self.generate_candidate(word_count, addresses_per_seed, matcher.format());
+                        seeds_generated.fetch_add(1, Ordering::Relaxed);
 
+                        let track_nearest = self.track_nearest.load(Ordering::Relaxed);
+                        let one_per_seed = self.one_per_seed.load(Ordering::Relaxed);
                         let mut local_results = Vec::new();
+                        let mut derived = 0;
                         for addr_info in addrs {
-                            if let Some(pattern) = matcher.is_match(&addr_info.address) {
+                            derived += 1;
+                            if track_nearest {
+                                self.update_nearest(matcher, &secure_seed, &addr_info.address, addr_info.position, actual_wc);
+                            }
+                            let patterns = matcher.matches_all(&addr_info.address);
+                            if !patterns.is_empty() && accept.as_ref().is_none_or(|f| f(&addr_info.address)) {
                                 local_results.push((
                                     secure_seed.clone(),  // Use the secure seed
                                     addr_info.address,
-                                    pattern.clone(),
+                                    patterns,
                                     addr_info.position,
                                     actual_wc,
+                                    seed_idx * addresses_per_seed as usize + addr_info.position as usize + 1,
                                 ));
+                                if one_per_seed {
+                                    break;
+                                }
                             }
                         }
+                        addresses_derived.fetch_add(derived, Ordering::Relaxed);
                         local_results
                     })
                     .collect();
 
             // Record timing for this batch
             let elapsed = start_time.elapsed();
-            {
-                let mut pm = self.performance_metrics.lock().unwrap();
-                pm.insert(0, elapsed);
-            }
+            *self.last_batch_duration.lock().unwrap() = Some(elapsed);
 
             // Update progress counters
-            self.progress.record_processed(
-                current_batch_size,
-                current_batch_size * addresses_per_seed as usize,
-            );
+            let batch_start = self.progress.total_addresses.load(Ordering::Relaxed);
+            self.progress.record_processed(seeds_generated.into_inner(), addresses_derived.into_inner());
 
             // Flatten results from all threads
-            let chunk = chunk.into_iter().flatten().collect::<Vec<_>>();
+            let mut chunk = chunk.into_iter().flatten().collect::<Vec<_>>();
+            if self.prefer_low_index.load(Ordering::Relaxed) {
+                let remaining = limit.saturating_sub(found_count.load(Ordering::SeqCst));
+                chunk = keep_lowest_indices(chunk, remaining, |candidate| candidate.3);
+            }
 
             // Move them into our global results, checking if we reached num_results
-            for (secure_seed, address, pattern, position, wc) in chunk {
-                if self.is_cancelled() {
+            for (secure_seed, address, patterns, position, wc, batch_offset) in chunk {
+                if self.is_cancelled(epoch) {
                     break;
                 }
-                {
-                    let mut pmatches = pattern_matches.lock().unwrap();
-                    *pmatches.entry(pattern.clone()).or_insert(0) += 1;
+
+                // Matches found in the same batch after every matched pattern's quota filled are dropped
+                if patterns.iter().all(|p| pattern_matches.lock().unwrap().get(p).copied().unwrap_or(0) >= quota) {
+                    continue;
                 }
-                
-                let total_found = found_count.fetch_add(1, Ordering::Relaxed) + 1;
-                
-                // Store the result
-                {
-                    let mut r = results.lock().unwrap();
-                    r.push((secure_seed.clone(), address.clone(), pattern.clone(), position, wc));
+
+                // Surplus matches beyond the limit are dropped
+                let total_found = match claim_result_slot(&found_count, limit) {
+                    Some(n) => n,
+                    None => break,
+                };
+
+                // An address containing several patterns counts toward each of their quotas,
+                // without pushing an already filled one past it
+                for matched in &patterns {
+                    let pattern_count = {
+                        let mut pmatches = pattern_matches.lock().unwrap();
+                        let count = pmatches.entry(matched.clone()).or_insert(0);
+                        *count = (*count + 1).min(quota);
+                        *count
+                    };
+                    if pattern_count >= quota && matcher.deactivate(matched) {
+                        log::debug!("Quota of {} reached for pattern {}, no longer testing it", quota, matched);
+                    }
                 }
-                
-                // If there's a user callback, invoke it
-                if let Some(callback) = self.result_callback.lock().unwrap().as_ref() {
-                    callback(secure_seed.as_str(), &address, &pattern, position, wc);
+
+                let pattern = patterns.join(", ");
+                let at = batch_start + batch_offset;
+                let checked = patterns
+                    .iter()
+                    .map(|p| addresses_since_last_match(&mut last_match_at, p, at))
+                    .min()
+                    .unwrap_or(0);
+                log::info!("{} seed=<masked>", compact_match_fields(total_found, &pattern, &address, position, wc, checked));
+
+                // Store the result; extras found with keep-going are only streamed
+                if total_found <= num_results {
+                    results.push((secure_seed.clone(), address.clone(), pattern.clone(), position, wc, checked));
                 }
+                self.found_total.fetch_add(1, Ordering::SeqCst);
                 
-                // Log match to console
-                if total_found <= 10 || total_found % 10 == 0 {
-                    println!("MATCH #{} found pattern: {}", total_found, pattern);
-                    println!("Address: {}", address);
-                    println!("Position: {}", position);
-                    println!("Seed phrase ({}-word): {}", wc, secure_seed.as_str());
-                    println!("---------------------------");
-                }
-
-                // If balanced matching, check if we have enough of this specific pattern
-                if let Some(max_per_pattern) = if matcher.has_multiple_patterns() {
-                    Some(1 + (num_results / pattern_matches.lock().unwrap().len()))
-                } else {
-                    None
-                } {
-                    if let Some(count) = pattern_matches.lock().unwrap().get(&pattern) {
-                        if *count >= max_per_pattern && total_found >= pattern_matches.lock().unwrap().len() {
-                            break;
-                        }
-                    }
+                self.report_match(epoch, secure_seed.as_str(), &address, &pattern, position, wc, checked);
+                
+                // Log match to console (every match in compact or keep-going mode)
+                if self.streams_every_match() || total_found <= 10 || total_found % 10 == 0 {
+                    self.print_match(total_found, secure_seed.as_str(), &address, &pattern, position, wc, checked);
                 }
 
                 // Stop if we have enough total matches
-                if total_found >= num_results {
+                if total_found >= limit {
                     break;
                 }
             }
-        }
 
-        // Fix for the borrow issue - clone the vector before the lock is dropped
-        let secure_results = {
-            let locked_results = results.lock().unwrap();
-            locked_results.clone()
-        };
+            // Addresses credited to several patterns can fill every quota with fewer than
+            // num_results results; nothing is left to search for then
+            if !matcher.has_active_patterns() {
+                break;
+            }
+        }
 
         // Convert secure results to exposed results at the end
-        self.convert_secure_to_exposed(secure_results)
+        self.convert_secure_to_exposed(results)
     }
 
     // -------------------------------------------
@@ -366,111 +1036,363 @@ impl AddressProcessor {
         word_count: usize,
         num_results: usize,
         addresses_per_seed: u32,
+        epoch: usize,
     ) -> Vec<MatchResult> {
+        let limit = self.match_limit(num_results);
         let found_count = Arc::new(AtomicUsize::new(0));
-        let results = Arc::new(Mutex::new(Vec::<SecureMatchResult>::new()));
+        // Filled only in the single-threaded merge after each parallel batch
+        let mut results = Vec::<SecureMatchResult>::new();
+        let mut last_match_at = HashMap::<String, usize>::new();
+        let accept = self.accept_predicate.lock().unwrap().clone();
 
         // Generate seed batches in parallel until we have enough matches
-        while found_count.load(Ordering::SeqCst) < num_results && !self.is_cancelled() {
-            if self.is_cancelled() {
+        while found_count.load(Ordering::SeqCst) < limit && !self.is_cancelled(epoch) {
+            if self.is_cancelled(epoch) {
                 break;
             }
+            self.check_word_count_fallback(word_count);
             let batch_num = self.batch_counter.fetch_add(1, Ordering::Relaxed);
             let current_batch_size = self.batch_size.load(Ordering::Relaxed);
 
             // Periodically adjust batch size
             if batch_num % self.batch_adjust_interval == 0 {
-                self.adjust_batch_size(0);
+                self.adjust_batch_size();
             }
 
             let start_time = Instant::now();
             
-            // Generate seeds in parallel and find addresses that match
-            let chunk: Vec<SecureMatchResult> = (0..current_batch_size)
+            // Generate seeds in parallel and find addresses that match, counting what is actually
+            // generated (see find_balanced_matches)
+            let all_per_seed = self.all_per_seed.load(Ordering::Relaxed);
+            let seeds_generated = AtomicUsize::new(0);
+            let addresses_derived = AtomicUsize::new(0);
+            let mut chunk: Vec<Candidate> = (0..current_batch_size)
                 .into_par_iter()
-                .filter_map(|_| {
-                    if self.is_cancelled() || found_count.load(Ordering::SeqCst) >= num_results {
-                        return None;
+                .flat_map_iter(|seed_idx| {
+                    let mut matches = Vec::new();
+                    if self.stop_requested(epoch) || found_count.load(Ordering::SeqCst) >= limit {
+                        return matches;
                     }
                     
                     // Generate one seed and check all derived addresses
-                    let (secure_seed, actual_wc) = generate_secure_mnemonic(word_count);
-                    let addrs = generate_addresses(secure_seed.as_str(), addresses_per_seed);
+                    let (secure_seed, actual_wc, addrs) = self.generate_candidate(word_count, addresses_per_seed, matcher.format());
+                    seeds_generated.fetch_add(1, Ordering::Relaxed);
                     
-                    // Return the first matching address for this seed (if any)
+                    // Keep the first matching address for this seed (if any), or every one with all-per-seed
+                    let track_nearest = self.track_nearest.load(Ordering::Relaxed);
+                    let mut derived = 0;
                     for addr_info in addrs {
-                        if let Some(pattern) = matcher.is_match(&addr_info.address) {
-                            return Some((
-                                secure_seed,
+                        derived += 1;
+                        if track_nearest {
+                            self.update_nearest(matcher, &secure_seed, &addr_info.address, addr_info.position, actual_wc);
+                        }
+                        let pattern = matcher.is_match(&addr_info.address)
+                            .filter(|_| accept.as_ref().is_none_or(|f| f(&addr_info.address)));
+                        if let Some(pattern) = pattern {
+                            matches.push((
+                                secure_seed.clone(),
                                 addr_info.address,
                                 pattern,
                                 addr_info.position,
-                                actual_wc
+                                actual_wc,
+                                seed_idx * addresses_per_seed as usize + addr_info.position as usize + 1,
                             ));
+                            if !all_per_seed {
+                                break;
+                            }
                         }
                     }
+                    addresses_derived.fetch_add(derived, Ordering::Relaxed);
                     
-                    None
+                    matches
                 })
                 .collect();
                 
             // Record timing
             let elapsed = start_time.elapsed();
-            {
-                let mut pm = self.performance_metrics.lock().unwrap();
-                pm.insert(0, elapsed);
-            }
+            *self.last_batch_duration.lock().unwrap() = Some(elapsed);
             
             // Record metrics
-            self.progress.record_processed(
-                current_batch_size,
-                current_batch_size * addresses_per_seed as usize,
-            );
-            
-            // Process only as many results as needed to reach num_results
-            let needed = num_results.saturating_sub(found_count.load(Ordering::SeqCst));
-            let to_take = needed.min(chunk.len());
+            let batch_start = self.progress.total_addresses.load(Ordering::Relaxed);
+            self.progress.record_processed(seeds_generated.into_inner(), addresses_derived.into_inner());
+            if self.prefer_low_index.load(Ordering::Relaxed) {
+                let remaining = limit.saturating_sub(found_count.load(Ordering::SeqCst));
+                chunk = keep_lowest_indices(chunk, remaining, |candidate| candidate.3);
+            }
             
-            for (secure_seed, address, pattern, position, wc) in chunk.into_iter().take(to_take) {
-                if self.is_cancelled() {
+            // Accept exactly as many results as needed to reach num_results
+            for (secure_seed, address, pattern, position, wc, batch_offset) in chunk {
+                if self.is_cancelled(epoch) {
                     break;
                 }
+
+                // Surplus matches beyond the limit are dropped
+                let total_found = match claim_result_slot(&found_count, limit) {
+                    Some(n) => n,
+                    None => break,
+                };
                 
-                let total_found = found_count.fetch_add(1, Ordering::Relaxed) + 1;
-                
-                // Store the result
-                {
-                    let mut r = results.lock().unwrap();
-                    r.push((secure_seed.clone(), address.clone(), pattern.clone(), position, wc));
+                let checked = addresses_since_last_match(&mut last_match_at, &pattern, batch_start + batch_offset);
+                log::info!("{} seed=<masked>", compact_match_fields(total_found, &pattern, &address, position, wc, checked));
+
+                // Store the result; extras found with keep-going are only streamed
+                if total_found <= num_results {
+                    results.push((secure_seed.clone(), address.clone(), pattern.clone(), position, wc, checked));
                 }
+                *self.pattern_tally.lock().unwrap().entry(pattern.clone()).or_insert(0) += 1;
+                self.found_total.fetch_add(1, Ordering::SeqCst);
                 
-                // If there's a user callback, invoke it
-                if let Some(callback) = self.result_callback.lock().unwrap().as_ref() {
-                    callback(secure_seed.as_str(), &address, &pattern, position, wc);
-                }
+                self.report_match(epoch, secure_seed.as_str(), &address, &pattern, position, wc, checked);
                 
-                // Log match to console
-                if total_found <= 10 || total_found % 10 == 0 {
-                    println!("MATCH #{} found pattern: {}", total_found, pattern);
-                    println!("Address: {}", address);
-                    println!("Position: {}", position);
-                    println!("Seed phrase ({}-word): {}", wc, secure_seed.as_str());
-                    println!("---------------------------");
+                // Log match to console (every match in compact or keep-going mode)
+                if self.streams_every_match() || total_found <= 10 || total_found % 10 == 0 {
+                    self.print_match(total_found, secure_seed.as_str(), &address, &pattern, position, wc, checked);
                 }
                 
-                if total_found >= num_results {
+                if total_found >= limit {
                     break;
                 }
             }
         }
         
-        // Fix for the borrow issue - clone the vector before the lock is dropped
-        let secure_results = {
-            let locked_results = results.lock().unwrap();
-            locked_results.clone()
-        };
-        
         // Convert secure results to exposed results at the end
-        self.convert_secure_to_exposed(secure_results)
+        self.convert_secure_to_exposed(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A quiet processor with small batches, so a test derives only a few seeds
+    fn test_processor() -> AddressProcessor {
+        let processor = AddressProcessor::new();
+        processor.set_quiet(true);
+        processor.set_batch_size_bounds(1, 8);
+        processor.set_initial_batch_size(4);
+        processor
+    }
+
+    #[test]
+    fn concurrent_claims_never_exceed_the_result_slots() {
+        let found_count = AtomicUsize::new(0);
+        let mut claimed: Vec<usize> = (0..64)
+            .into_par_iter()
+            .filter_map(|_| claim_result_slot(&found_count, 10))
+            .collect();
+        claimed.sort_unstable();
+        assert_eq!(claimed, (1..=10).collect::<Vec<_>>());
+        assert_eq!(found_count.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn a_search_reports_exactly_num_results() {
+        let processor = test_processor();
+        processor.set_simulate(true);
+        let reported = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&reported);
+        processor.set_result_callback(move |_, _, _, _, _, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        // Almost every candidate matches, so each batch finds more than the few slots left
+        let matcher = PatternMatcher::new(vec!["e".to_string()], false, false, false);
+        let results = processor.find_matches(matcher, 12, 5, false, 4);
+        assert_eq!(results.len(), 5);
+        assert_eq!(reported.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn max_results_caps_keep_going_searches() {
+        let processor = test_processor();
+        processor.set_simulate(true);
+        processor.set_keep_going(true);
+        processor.set_max_results(Some(3));
+        let matcher = PatternMatcher::new(vec!["e".to_string()], false, false, false);
+        assert_eq!(processor.find_matches(matcher, 12, 1, false, 4).len(), 1);
+        assert_eq!(processor.found_total.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn long_keep_going_runs_keep_their_state_bounded() {
+        let processor = Arc::new(test_processor());
+        processor.set_simulate(true);
+        processor.set_keep_going(true);
+        let search = Arc::clone(&processor);
+        let handle = std::thread::spawn(move || {
+            let matcher = PatternMatcher::new(vec!["e".to_string()], false, false, false);
+            search.find_matches(matcher, 12, 3, false, 4)
+        });
+
+        // Run until the rate history has been full for a few more progress updates
+        let started = Instant::now();
+        let mut full_since = None;
+        while full_since.is_none_or(|at: Instant| at.elapsed() < Duration::from_millis(1500)) {
+            assert!(started.elapsed() < Duration::from_secs(20), "the rate history never filled");
+            let kept = processor.progress.rate_history_len();
+            assert!(kept <= crate::progress::RATE_HISTORY_LEN);
+            if kept == crate::progress::RATE_HISTORY_LEN && full_since.is_none() {
+                full_since = Some(Instant::now());
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        processor.cancel();
+        let results = handle.join().unwrap();
+
+        // Thousands of batches and matches later, only --num results are stored, the tally has
+        // one entry per pattern and a single batch duration is kept for adaptive sizing
+        assert!(processor.batch_counter.load(Ordering::Relaxed) > 100);
+        assert!(processor.found_total.load(Ordering::SeqCst) > 100);
+        assert_eq!(results.len(), 3);
+        assert_eq!(processor.pattern_tally.lock().unwrap().len(), 1);
+        assert!(processor.last_batch_duration.lock().unwrap().is_some());
+        assert_eq!(processor.progress.rate_history_len(), crate::progress::RATE_HISTORY_LEN);
+    }
+
+    #[test]
+    fn processors_report_and_cancel_independently() {
+        // Two concurrent searches that never finish on their own, each counting its progress updates
+        let searches = (0..2)
+            .map(|_| {
+                let processor = Arc::new(test_processor());
+                processor.set_simulate(true);
+                let updates = Arc::new(AtomicUsize::new(0));
+                let counter = Arc::clone(&updates);
+                processor.set_progress_callback(move |_, _, _, _| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+                let search = Arc::clone(&processor);
+                let handle = std::thread::spawn(move || {
+                    let matcher = PatternMatcher::new(vec!["zzzzzzzz".to_string()], true, false, false);
+                    search.find_matches(matcher, 12, 1, false, 1)
+                });
+                (processor, updates, handle)
+            })
+            .collect::<Vec<_>>();
+        std::thread::sleep(Duration::from_millis(1_200));
+        assert!(searches.iter().all(|(_, updates, _)| updates.load(Ordering::SeqCst) > 0));
+
+        // Cancelling the first leaves the second searching and reporting
+        let mut searches = searches.into_iter();
+        let (first, _, first_search) = searches.next().unwrap();
+        let (second, second_updates, second_search) = searches.next().unwrap();
+        first.cancel();
+        assert!(first_search.join().unwrap().is_empty());
+        let before = second_updates.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(1_200));
+        assert!(!second_search.is_finished());
+        assert!(second_updates.load(Ordering::SeqCst) > before);
+        second.cancel();
+        assert!(second_search.join().unwrap().is_empty());
+    }
+
+    #[test]
+    fn one_per_seed_never_shares_a_seed() {
+        let processor = test_processor();
+        processor.set_one_per_seed(true);
+        // Every mainnet address starts with '9', so each derived address contains it
+        let matcher = PatternMatcher::new(vec!["9".to_string()], false, false, false);
+        let results = processor.find_matches(matcher, 12, 6, true, 5);
+
+        assert_eq!(results.len(), 6);
+        let mut seeds: Vec<&str> = results.iter().map(|(seed, ..)| seed.as_str()).collect();
+        seeds.sort_unstable();
+        seeds.dedup();
+        assert_eq!(seeds.len(), results.len());
+    }
+
+    #[test]
+    fn one_per_seed_counts_only_the_addresses_derived() {
+        for balanced in [false, true] {
+            let processor = test_processor();
+            processor.set_one_per_seed(true);
+            // The first address of every seed matches, so none of the other 999 are derived
+            let matcher = PatternMatcher::new(vec!["9".to_string()], false, false, false);
+            let results = processor.find_matches(matcher, 12, 3, balanced, 1000);
+
+            assert_eq!(results.len(), 3);
+            let seeds = processor.progress.total_seeds.load(Ordering::Relaxed);
+            assert!(seeds >= 3);
+            assert_eq!(processor.progress.total_addresses.load(Ordering::Relaxed), seeds, "balanced: {}", balanced);
+        }
+    }
+
+    #[test]
+    fn balanced_matches_credit_every_pattern_in_the_address() {
+        let processor = test_processor();
+        processor.set_simulate(true);
+        // Only addresses with an 'a' get through, and every address contains a '9', so each
+        // match holds both patterns and fills a slot of both quotas
+        processor.set_accept_predicate(|address: &str| address.contains('a'));
+        let matcher = PatternMatcher::new(vec!["9".to_string(), "a".to_string()], false, false, false);
+        let results = processor.find_matches(matcher, 12, 4, true, 1);
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, address, pattern, ..)| pattern == "9, a" && address.contains('a')));
+    }
+
+    #[test]
+    fn cancel_and_reset_never_leak_monitoring_threads() {
+        let processor = Arc::new(test_processor());
+        processor.set_simulate(true);
+        for round in 0..40 {
+            let search = Arc::clone(&processor);
+            let handle = std::thread::spawn(move || {
+                let matcher = PatternMatcher::new(vec!["e".to_string()], false, false, false);
+                search.find_matches(matcher, 12, usize::MAX, false, 1);
+            });
+            let started = Instant::now();
+            while !processor.progress.is_monitoring() && started.elapsed() < Duration::from_secs(5) {
+                std::thread::yield_now();
+            }
+            assert!(processor.progress.is_monitoring(), "round {}: the search never started", round);
+
+            processor.cancel();
+            processor.reset();
+            // stop() joined the monitor, which drops its handle and its clone of the counters
+            assert!(!processor.progress.is_monitoring(), "round {}", round);
+            assert_eq!(Arc::strong_count(&processor.progress.total_seeds), 1, "round {}", round);
+            handle.join().unwrap();
+            assert!(!processor.progress.is_monitoring(), "round {}", round);
+        }
+    }
+
+    #[test]
+    fn restarting_never_leaks_results_across_searches() {
+        const ROUNDS: usize = 60;
+        let processor = Arc::new(test_processor());
+        processor.set_simulate(true);
+        let charset: Vec<char> = AddressFormat::default().charset().chars().collect();
+        let delivered = Arc::new(AtomicUsize::new(0));
+        let leaked = Arc::new(AtomicUsize::new(0));
+
+        // Each round searches for its own character and restarts before the previous search
+        // has wound down, so only a stale batch could report another round's pattern
+        let mut searches = Vec::new();
+        for round in 0..ROUNDS {
+            processor.reset();
+            let label = charset[round % charset.len()].to_string();
+            let expected = label.clone();
+            let (delivered, leaked) = (Arc::clone(&delivered), Arc::clone(&leaked));
+            processor.set_result_callback(move |_, _, pattern: &str, _, _, _| {
+                let counter = if pattern == expected { &delivered } else { &leaked };
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+            let search = Arc::clone(&processor);
+            searches.push(std::thread::spawn(move || {
+                let matcher = PatternMatcher::new(vec![label], true, false, false);
+                search.find_matches(matcher, 12, usize::MAX, false, 1);
+            }));
+            std::thread::sleep(Duration::from_millis(1 + (round % 4) as u64));
+            if round % 3 == 0 {
+                processor.cancel();
+            }
+        }
+        processor.cancel();
+        for search in searches {
+            search.join().unwrap();
+        }
+
+        assert_eq!(leaked.load(Ordering::SeqCst), 0);
+        assert!(delivered.load(Ordering::SeqCst) > 0);
     }
 }
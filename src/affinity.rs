@@ -0,0 +1,82 @@
+//! Pinning worker threads to CPU cores (`--cpu-affinity`).
+//!
+//! Linux and Windows pin threads to the cores given; macOS only treats them as a scheduling
+//! hint. Where the core list can't be read, pinning is skipped and threads run unpinned.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Sorted, distinct core ids as given to `--cpu-affinity`
+pub type CoreList = Vec<usize>;
+
+/// Parses a core list such as `0-3,6` into sorted, distinct core ids
+pub fn parse_core_list(value: &str) -> Result<CoreList, String> {
+    let parse = |n: &str| n.trim().parse::<usize>().map_err(|_| format!("'{}' is not a core number", n.trim()));
+    let mut cores = Vec::new();
+    for item in value.split(',') {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if first > last {
+                    return Err(format!("'{}' is not an ascending range", item.trim()));
+                }
+                cores.extend(first..=last);
+            }
+            None => cores.push(parse(item)?),
+        }
+    }
+    cores.sort_unstable();
+    cores.dedup();
+    Ok(cores)
+}
+
+/// Shows a core list compactly, e.g. `0-3,6`
+pub fn format_core_list(cores: &[usize]) -> String {
+    cores
+        .chunk_by(|a, b| a + 1 == *b)
+        .map(|run| match run {
+            [only] => only.to_string(),
+            [first, .., last] => format!("{}-{}", first, last),
+            [] => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Checks that every one of `cores` exists on this machine. Returns `Ok(false)` where the
+/// cores can't be listed, so callers can warn and carry on unpinned.
+pub fn check_cores(cores: &[usize]) -> Result<bool, String> {
+    let Some(available) = core_affinity::get_core_ids() else {
+        return Ok(false);
+    };
+    let available: Vec<usize> = available.into_iter().map(|core| core.id).collect();
+    match cores.iter().find(|core| !available.contains(core)) {
+        Some(core) => Err(format!(
+            "core {} does not exist (this machine has cores {})",
+            core,
+            format_core_list(&available)
+        )),
+        None => Ok(true),
+    }
+}
+
+/// Pins the calling thread, worker `index` of a pool, to one of `cores` in turn, and counts
+/// it in `pinned` if the OS accepted
+pub fn pin_worker(cores: &[usize], index: usize, pinned: &AtomicUsize) {
+    let core = cores[index % cores.len()];
+    if core_affinity::set_for_current(core_affinity::CoreId { id: core }) {
+        pinned.fetch_add(1, Ordering::Relaxed);
+    } else {
+        log::warn!("Could not pin worker thread {} to core {}", index, core);
+    }
+}
+
+/// Builds the Rayon global pool (used by `--benchmark` and `--autotune`) with one thread per
+/// core in `cores`, each pinned to its core. Must run before anything uses the global pool.
+pub fn pin_global_pool(cores: &[usize]) -> Result<(), String> {
+    let cores = cores.to_vec();
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(cores.len())
+        .start_handler(move |index| pin_worker(&cores, index, &AtomicUsize::new(0)))
+        .build_global()
+        .map_err(|e| e.to_string())
+}
@@ -1,22 +1,93 @@
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use clap::Parser;
-use crate::matcher::PatternMatcher;
+use crate::matcher::{parse_class_run, parse_pattern_spec, CharClass, MatchMode, PatternMatcher, DEFAULT_CLEAN_CHARS};
+use crate::utils::{EntropySource, SeedOutputFormat, WordMix, ERGO_COIN_TYPE, MAX_WORD_MIX_WEIGHT};
+use crate::address_processor::AddressProcessor;
+use crate::estimator::{self, DEFAULT_MAX_EXPECTED_DAYS};
+use crate::metadata::sanitize_label;
+
+/// Addresses per seed above which a search mostly re-derives addresses nobody will use
+const LARGE_ADDRESSES_PER_SEED: u32 = 1000;
 
 /// A high-performance vanity address generator for the Ergo blockchain
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(group(clap::ArgGroup::new("match_output").args(["event_socket", "jsonl"]).multiple(true)))]
+#[command(group(clap::ArgGroup::new("match_file").args(["jsonl", "jobs"]).multiple(true)))]
+#[command(group(clap::ArgGroup::new("sequence").args(["seed_sequence", "resume_manifest"])))]
 pub struct Args {
-    /// Pattern(s) to search for, comma-separated for multiple patterns
-    #[arg(short, long, value_delimiter = ',')]
+    /// Pattern(s) to search for, comma-separated for multiple patterns.
+    /// Prefix a pattern with `start:`, `end:` or `any:` to give it its own match mode,
+    /// e.g. `start:efg,end:xyz,any:cafe`; `checksum:777` matches within the last 6 characters
+    #[arg(short, long, value_delimiter = ',', env = "VANITYGEN_PATTERNS")]
     pub patterns: Vec<String>,
 
     /// Match at start of address only (after the first '9')
-    #[arg(short, long)]
+    #[arg(short, long, env = "VANITYGEN_START")]
     pub start: bool,
 
     /// Match at end of address only
-    #[arg(short, long)]
+    #[arg(short, long, env = "VANITYGEN_END")]
     pub end: bool,
 
+    /// When more matches turn up at once than are needed, keep the ones at the lowest
+    /// address indices (index 0 is the wallet's first address)
+    #[arg(long = "prefer-low-index")]
+    pub prefer_low_index: bool,
+
+    /// Keep at most one match per seed, skipping a seed's remaining addresses once one matches
+    /// (for more seed diversity with several addresses per seed)
+    #[arg(long = "one-per-seed")]
+    pub one_per_seed: bool,
+
+    /// Report every matching address of a seed, not just the first, in non-balanced searches.
+    /// Each one counts towards --num
+    #[arg(long = "all-per-seed", conflicts_with = "one_per_seed")]
+    pub all_per_seed: bool,
+
+    /// Show live stats, matches and a log in a terminal UI (for SSH and headless machines);
+    /// seeds are printed when you quit. Falls back to plain output when not in a terminal
+    #[arg(long, conflicts_with = "event_socket")]
+    pub tui: bool,
+
+    /// Match end patterns just before the last 6 characters, which the checksum dominates,
+    /// instead of at the very end of the address
+    #[arg(long = "end-before-checksum")]
+    pub end_before_checksum: bool,
+
+    /// Also match any dictionary word (from the BIP39 English list) anywhere in the address and
+    /// report which word was found; patterns become optional
+    #[arg(long, conflicts_with = "balanced")]
+    pub dictionary: bool,
+
+    /// Shortest word that counts as a match with --dictionary
+    #[arg(long = "dictionary-min-len", value_name = "N", default_value_t = 5, requires = "dictionary")]
+    pub dictionary_min_len: usize,
+
+    /// Also match addresses whose first N characters after the "9e"-style prefix are all
+    /// digits, uppercase or lowercase letters (e.g. `4:digit`); patterns become optional
+    #[arg(long = "class-run", value_name = "N:CLASS", value_parser = parse_class_run, conflicts_with = "balanced")]
+    pub class_run: Option<(usize, CharClass)>,
+
+    /// Require anywhere patterns to occur exactly N times in the address (e.g. 1 for a pattern
+    /// that appears once and is not repeated) instead of at least once
+    #[arg(long = "exact-count", value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub exact_count: Option<u16>,
+
+    /// Treat patterns as shape templates: `L` matches any letter, `D` any digit, and other
+    /// characters match themselves (e.g. `-p LLDDLL --shape`)
+    #[arg(long)]
+    pub shape: bool,
+
+    /// Reject matching addresses that contain hard-to-read characters anywhere (see --clean-chars)
+    #[arg(long)]
+    pub clean: bool,
+
+    /// Characters rejected by --clean
+    #[arg(long = "clean-chars", value_name = "CHARS", default_value = DEFAULT_CLEAN_CHARS, requires = "clean")]
+    pub clean_chars: String,
+
     /// Case-sensitive matching (default: case-insensitive)
     #[arg(short = 'm', long = "matchCase")]
     pub case_sensitive: bool,
@@ -33,25 +104,299 @@ pub struct Args {
     #[arg(long = "wany")]
     pub any_word_length: bool,
 
+    /// Relative weights of 12, 15 and 24-word seeds with --wany, e.g. 2,1,1 (default: 1,1,1)
+    #[arg(long = "word-mix", value_name = "W12,W15,W24", requires = "any_word_length", value_parser = parse_word_mix)]
+    pub word_mix: Option<WordMix>,
+
     /// Number of addresses to check per seed (default: 1)
-    #[arg(short, long, default_value_t = 1)]
+    #[arg(short, long, default_value_t = 1, env = "VANITYGEN_ADDRESSES_PER_SEED")]
     pub addresses_per_seed: u32,
 
+    /// BIP44 coin type to derive addresses under (m/44'/N'/0'/0/index). Experimental: standard
+    /// Ergo wallets only derive addresses under 429'
+    #[arg(long = "coin-type", value_name = "N", default_value_t = ERGO_COIN_TYPE,
+          value_parser = clap::value_parser!(u32).range(..0x8000_0000), conflicts_with = "jobs")]
+    pub coin_type: u32,
+
     /// Number of matches to find (default: 1)
-    #[arg(short, long = "num", default_value_t = 1)]
+    #[arg(short, long = "num", default_value_t = 1, env = "VANITYGEN_NUM")]
     pub num: usize,
 
     /// Try to find equal matches for all patterns (longer search times)
-    #[arg(long)]
+    #[arg(long, env = "VANITYGEN_BALANCED")]
     pub balanced: bool,
 
     /// Estimate difficulty and time for the given pattern
     #[arg(long)]
     pub estimate: bool,
 
+    /// Addresses per second to assume in --estimate and the --max-expected-days check (default:
+    /// the rate measured by the last search or benchmark, or 6,000-12,000 if none was recorded)
+    #[arg(long, value_name = "ADDR_PER_SEC")]
+    pub rate: Option<f64>,
+
+    /// Also print how many addresses to check for this chance (0-1) of at least one match
+    #[arg(long, value_name = "PROBABILITY", requires = "estimate")]
+    pub confidence: Option<f64>,
+
+    /// Delete the cached measured rate used by --estimate, then exit
+    #[arg(long = "reset-rate-cache")]
+    pub reset_rate_cache: bool,
+
+    /// Benchmark seed generation for 12, 15 and 24-word seeds and print a comparison
+    #[arg(long)]
+    pub benchmark: bool,
+
+    /// Sweep batch sizes, print the fastest and use it for this run (if patterns are given);
+    /// the result is saved and reused as the starting batch size by later runs
+    #[arg(long)]
+    pub autotune: bool,
+
+    /// Use exactly this many seeds per batch; disables adaptive batch sizing
+    #[arg(long = "batch-size", value_name = "SEEDS", conflicts_with = "autotune")]
+    pub batch_size: Option<usize>,
+
+    /// Lower bound for adaptive batch sizing (default derived from CPU features)
+    #[arg(long = "min-batch-size", value_name = "SEEDS")]
+    pub min_batch_size: Option<usize>,
+
+    /// Upper bound for adaptive batch sizing (default derived from CPU features)
+    #[arg(long = "max-batch-size", value_name = "SEEDS")]
+    pub max_batch_size: Option<usize>,
+
+    /// Run one worker thread per listed core, pinned to it (e.g. 0-3,6), for steadier
+    /// benchmarks or to leave other cores free. Ignored where the OS doesn't support it
+    #[arg(long = "cpu-affinity", value_name = "CORES", value_parser = crate::affinity::parse_core_list)]
+    pub cpu_affinity: Option<crate::affinity::CoreList>,
+
+    /// Benchmark pattern matching alone with 1, 10, 100 and 1,000 anywhere patterns
+    #[arg(long = "benchmark-matcher")]
+    pub benchmark_matcher: bool,
+
+    /// Number of seeds generated per word length in --benchmark
+    #[arg(long = "benchmark-seeds", default_value_t = 500)]
+    pub benchmark_seeds: usize,
+
+    /// Treat duplicate patterns as an error instead of ignoring them with a warning
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Check every pattern before failing and list all invalid ones, treating empty patterns
+    /// (e.g. from a trailing comma) as invalid too, so automation fails loudly on a typo
+    #[arg(long = "strict-patterns")]
+    pub strict_patterns: bool,
+
+    /// Print version, ergo-lib version, enabled features and CPU features, then exit
+    #[arg(long = "build-info")]
+    pub build_info: bool,
+
+    /// Run a short end-to-end search and verify its results (checksum, derivation, pattern), then exit
+    #[arg(long = "self-test")]
+    pub self_test: bool,
+
+    /// Decrypt the seed from an encrypted paper wallet QR payload (read from stdin), then exit
+    #[arg(long = "decrypt-wallet")]
+    pub decrypt_wallet: bool,
+
+    /// Print the seed phrase and addresses for your own hex entropy (read from stdin), then exit.
+    /// Advanced: the wallet is only as safe as the entropy you supply
+    #[arg(long = "from-entropy")]
+    pub from_entropy: bool,
+
+    /// Check whether this address was derived from a seed phrase (read from stdin) and print
+    /// its derivation path, then exit
+    #[arg(long, value_name = "ADDRESS")]
+    pub verify: Option<String>,
+
+    /// Address indices searched per account with --verify
+    #[arg(long = "verify-indices", value_name = "N", default_value_t = 100, requires = "verify")]
+    pub verify_indices: u32,
+
+    /// Accounts searched with --verify (m/44'/429'/ACCOUNT'/0/index, see --coin-type)
+    #[arg(long = "verify-accounts", value_name = "N", default_value_t = 1, requires = "verify")]
+    pub verify_accounts: u32,
+
+    /// Check the mnemonics in this file (one per line) for matches instead of generating new seeds
+    #[arg(long = "seeds-file", value_name = "PATH")]
+    pub seeds_file: Option<String>,
+
+    /// FOR TESTING ONLY: check a reproducible sequence of seeds derived from this hex entropy
+    /// (128, 160 or 256 bits) instead of random ones. Never use these seeds for real funds
+    #[arg(long = "seed-sequence", value_name = "HEX", conflicts_with = "seeds_file")]
+    pub seed_sequence: Option<String>,
+
+    /// Number of seeds checked with --seed-sequence or --resume-manifest
+    #[arg(long = "seed-sequence-count", value_name = "N", default_value_t = 1000, requires = "sequence")]
+    pub seed_sequence_count: usize,
+
+    /// Number of the first seed checked with --seed-sequence (counting from 1)
+    #[arg(long = "seed-sequence-start", value_name = "N", default_value_t = 1, requires = "seed_sequence",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pub seed_sequence_start: u64,
+
+    /// Record the --seed-sequence search (entropy, criteria, seeds checked, matches) in a new
+    /// JSON manifest at PATH (under --output-dir when relative) that others can resume or replay
+    #[arg(long, value_name = "PATH", requires = "seed_sequence")]
+    pub manifest: Option<PathBuf>,
+
+    /// Continue the search of a manifest for --seed-sequence-count more seeds, with its
+    /// criteria, and add them to it
+    #[arg(long = "resume-manifest", value_name = "PATH", conflicts_with = "seeds_file")]
+    pub resume_manifest: Option<PathBuf>,
+
+    /// Check every seed of a manifest again and confirm that it finds exactly the recorded matches
+    #[arg(long = "replay-manifest", value_name = "PATH", conflicts_with_all = ["seeds_file", "sequence"])]
+    pub replay_manifest: Option<PathBuf>,
+
+    /// Write flamegraph-friendly timings of seed generation, derivation and matching to PATH
+    #[cfg(feature = "profile")]
+    #[arg(long, value_name = "PATH")]
+    pub profile: Option<String>,
+
+    /// Print machine-readable JSON output (with --build-info or --estimate)
+    #[arg(long)]
+    pub json: bool,
+
     /// Disable GUI (use command-line only)
     #[arg(long = "no-gui")]
     pub no_gui: bool,
+
+    /// Never draw the progress spinner, e.g. for CI logs; the final summary is still printed
+    #[arg(long = "no-progress")]
+    pub no_progress: bool,
+
+    /// Seed entropy source: `os` reads the OS RNG (getrandom) for every seed, `csprng` uses
+    /// rand's thread-local generator seeded from the OS (faster)
+    #[arg(long, value_enum, default_value_t = EntropySource::Csprng)]
+    pub entropy: EntropySource,
+
+    /// How to print found seeds: BIP39 words, raw entropy hex, or both
+    #[arg(long = "seed-output-format", value_enum, default_value_t = SeedOutputFormat::Words)]
+    pub seed_output_format: SeedOutputFormat,
+
+    /// Never print seeds as text; show them only as a QR code (the QR still contains the secret)
+    #[arg(long = "qr-only-seed")]
+    pub qr_only_seed: bool,
+
+    /// Never use the clipboard in the GUI: copy buttons save to a file instead, for air-gapped
+    /// machines where clipboard managers could leak seeds
+    #[arg(long = "file-only")]
+    pub file_only: bool,
+
+    /// Start the GUI with "Verify address QR codes" ticked, so paper wallets decode their
+    /// address QR codes back before saving. Seed QR codes are always verified
+    #[arg(long = "verify-qr")]
+    pub verify_qr: bool,
+
+    /// Print each match on one line: `MATCH n=1 pattern=efg addr=9ef... pos=0 words=24 ... seed="..."`
+    #[arg(long, conflicts_with = "qr_only_seed")]
+    pub compact: bool,
+
+    /// Replace seeds with <masked> in console match output (use with --event-socket or logs)
+    #[arg(long = "mask-seed")]
+    pub mask_seed: bool,
+
+    /// Stop the search after this many seconds
+    #[arg(long, value_name = "SECS", env = "VANITYGEN_TIMEOUT")]
+    pub timeout: Option<u64>,
+
+    /// Switch to 12-word seeds (quicker to generate) if nothing has matched after this many
+    /// seconds. For when any match matters more than the seed length
+    #[arg(long = "max-word-count-fallback", value_name = "SECS", conflicts_with = "twelve_word")]
+    pub word_count_fallback: Option<u64>,
+
+    /// Keep searching after --num matches until Ctrl+C or --timeout, printing every extra match.
+    /// Only the first --num matches are kept in memory
+    #[arg(long = "keep-going")]
+    pub keep_going: bool,
+
+    /// Hard cap: stop once this many matches are found, even with --keep-going
+    #[arg(long = "max-results", value_name = "N")]
+    pub max_results: Option<usize>,
+
+    /// Start the search even if it is expected to take longer than --max-expected-days
+    #[arg(long)]
+    pub force: bool,
+
+    /// Refuse searches expected to take longer than this many days (at a conservative speed)
+    /// unless --force is given. Not checked when --timeout bounds the search
+    #[arg(long = "max-expected-days", value_name = "DAYS", default_value_t = DEFAULT_MAX_EXPECTED_DAYS)]
+    pub max_expected_days: f64,
+
+    /// If the timeout hits with no exact match, report the closest near miss (by edit distance)
+    #[arg(long, requires = "timeout")]
+    pub nearest: bool,
+
+    /// Simulate: match random Base58 strings instead of real derived addresses (testing only,
+    /// results are NOT real wallets)
+    #[arg(long)]
+    pub simulate: bool,
+
+    /// Directory for generated files such as paper wallets, created if missing
+    #[arg(long = "output-dir", value_name = "DIR", env = "VANITYGEN_OUTPUT_DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Write each match as a JSON line to this Unix socket (named pipe on Windows)
+    #[arg(long = "event-socket", value_name = "PATH")]
+    pub event_socket: Option<String>,
+
+    /// Include the seed phrase in event socket messages (sensitive!)
+    #[arg(long = "event-include-seed", requires = "event_socket", conflicts_with = "qr_only_seed")]
+    pub event_include_seed: bool,
+
+    /// Add generation time, tool version, derivation path, network and word count to each event
+    #[arg(long = "include-metadata", requires = "match_output")]
+    pub include_metadata: bool,
+
+    /// Append each match as a JSON line to this file as soon as it is found, flushed to disk,
+    /// so a crash never loses matches (relative paths go under --output-dir)
+    #[arg(long, value_name = "PATH")]
+    pub jsonl: Option<PathBuf>,
+
+    /// Name for this search, recorded in the summary, the progress log and every match event.
+    /// Reduced to letters, digits, `-`, `_` and `.`
+    #[arg(long, value_name = "NAME", value_parser = parse_label)]
+    pub label: Option<String>,
+
+    /// Leave the seed phrase out of --jsonl and --jobs output
+    #[arg(long = "no-seed", requires = "match_file")]
+    pub no_seed: bool,
+
+    /// Run the searches listed in this TOML file one after another, writing each job's matches
+    /// to <label>.jsonl in --output-dir, then exit
+    #[arg(long, value_name = "PATH")]
+    pub jobs: Option<PathBuf>,
+
+    /// With --jobs, run the remaining jobs after one fails instead of stopping
+    #[arg(long = "continue-on-error", requires = "jobs")]
+    pub continue_on_error: bool,
+
+    /// Increase diagnostic logging on stderr (-v info, -vv debug, -vvv trace); RUST_LOG overrides
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+/// Parses `--label`, keeping only the characters `sanitize_label` allows
+fn parse_label(value: &str) -> Result<String, String> {
+    sanitize_label(value).ok_or_else(|| "the label needs at least one letter, digit, '-', '_' or '.'".to_string())
+}
+
+/// Parses `--word-mix` as three comma-separated weights, not all zero
+fn parse_word_mix(value: &str) -> Result<WordMix, String> {
+    let weights: Vec<u32> = value
+        .split(',')
+        .map(|w| w.trim().parse::<u32>().map_err(|_| format!("'{}' is not a whole number", w.trim())))
+        .collect::<Result<_, _>>()?;
+    let mix: WordMix = weights
+        .try_into()
+        .map_err(|_| "expected three weights for 12, 15 and 24 words, e.g. 2,1,1".to_string())?;
+    if mix.iter().all(|&w| w == 0) {
+        return Err("at least one weight must be greater than 0".to_string());
+    }
+    if mix.iter().any(|&w| w > MAX_WORD_MIX_WEIGHT) {
+        return Err(format!("weights must be at most {}", MAX_WORD_MIX_WEIGHT));
+    }
+    Ok(mix)
 }
 
 impl Args {
@@ -68,23 +413,268 @@ impl Args {
         }
     }
 
+    /// The adaptive batch size range: CPU-derived defaults overridden by
+    /// `--min-batch-size`/`--max-batch-size`.
+    pub fn batch_size_bounds(&self) -> (usize, usize) {
+        let (default_min, default_max) = AddressProcessor::default_batch_size_bounds();
+        (
+            self.min_batch_size.unwrap_or(default_min),
+            self.max_batch_size.unwrap_or(default_max),
+        )
+    }
+
+    /// Checks that batch size flags are positive and ordered min <= batch size <= max.
+    pub fn validate_batch_sizes(&self) -> Result<(), String> {
+        for (flag, value) in [
+            ("--batch-size", self.batch_size),
+            ("--min-batch-size", self.min_batch_size),
+            ("--max-batch-size", self.max_batch_size),
+        ] {
+            if value == Some(0) {
+                return Err(format!("{} must be greater than 0", flag));
+            }
+        }
+
+        let (min, max) = self.batch_size_bounds();
+        if min > max {
+            return Err(format!("Minimum batch size {} is larger than the maximum {}", min, max));
+        }
+        if let Some(size) = self.batch_size {
+            if self.min_batch_size.is_some_and(|min| size < min) || self.max_batch_size.is_some_and(|max| size > max) {
+                return Err(format!("Batch size {} is outside the range {}..={}", size, min, max));
+            }
+        }
+        Ok(())
+    }
+
     /// Validates the arguments by delegating to the pattern matcher validation logic.
     pub fn validate(&self) -> Result<(), String> {
+        if self.dictionary && !cfg!(feature = "dictionary") {
+            return Err("This build has no dictionary (build with --features dictionary)".to_string());
+        }
         // Check if patterns are provided when running in CLI mode
-        if self.patterns.is_empty() {
+        if self.patterns.is_empty() && !self.dictionary && self.class_run.is_none() {
             return Err("At least one pattern must be specified when running in command-line mode".to_string());
         }
+        // No addresses per seed means nothing is ever checked and the search never ends
+        if self.addresses_per_seed == 0 {
+            return Err("--addresses-per-seed must be at least 1".to_string());
+        }
+        if self.max_results == Some(0) {
+            return Err("--max-results must be at least 1".to_string());
+        }
         
-        self.create_matcher().validate()
+        let matcher = self.create_matcher();
+        if self.strict_patterns {
+            let errors = matcher.pattern_errors();
+            if !errors.is_empty() {
+                return Err(format!(
+                    "{} of {} patterns are invalid (--strict-patterns):\n  {}",
+                    errors.len(),
+                    matcher.pattern_count(),
+                    errors.join("\n  ")
+                ));
+            }
+        }
+        if self.strict {
+            if let Some(duplicate) = matcher.duplicates().first() {
+                return Err(format!("Duplicate pattern '{}' (--strict)", duplicate));
+            }
+        }
+        if self.dictionary && matcher.dictionary_size() == 0 {
+            return Err(format!("No dictionary words have at least {} letters", self.dictionary_min_len));
+        }
+        matcher.validate()?;
+
+        if self.max_expected_days.is_nan() || self.max_expected_days <= 0.0 {
+            return Err("--max-expected-days must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// `path` resolved against `--output-dir` when relative
+    fn in_output_dir(&self, path: &Path) -> PathBuf {
+        match &self.output_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// The `--jsonl` file, resolved against `--output-dir` when relative
+    pub fn jsonl_path(&self) -> Option<PathBuf> {
+        self.jsonl.as_deref().map(|path| self.in_output_dir(path))
+    }
+
+    /// The new `--manifest` file, resolved against `--output-dir` when relative
+    pub fn manifest_path(&self) -> Option<PathBuf> {
+        self.manifest.as_deref().map(|path| self.in_output_dir(path))
+    }
+
+    /// The `--profile` output, resolved against `--output-dir` when relative
+    #[cfg(feature = "profile")]
+    pub fn profile_path(&self) -> Option<PathBuf> {
+        self.profile.as_deref().map(|path| self.in_output_dir(Path::new(path)))
+    }
+
+    /// Patterns as `(pattern, mode)` for the estimator
+    pub fn estimated_patterns(&self) -> Vec<(String, MatchMode)> {
+        self.pattern_specs()
+            .into_iter()
+            .map(|(mode, pattern, _)| (pattern, mode))
+            .collect()
+    }
+
+    /// Addresses the search is expected to need, for showing expected progress. Dictionary
+    /// words aren't estimated, so there is none in `--dictionary` mode, and a `--class-run`
+    /// is only estimated on its own.
+    pub fn expected_addresses(&self) -> Option<f64> {
+        if self.dictionary {
+            return None;
+        }
+        match self.class_run {
+            Some((len, class)) if self.patterns.is_empty() => {
+                let estimate = estimator::estimate_class_run(len, class, crate::utils::AddressFormat::default());
+                Some(estimate.attempts_needed * self.num as f64)
+            }
+            Some(_) => None,
+            None => Some(estimator::expected_search_addresses(&self.estimated_patterns(), self.num, self.balanced, self.shape, crate::utils::p2pk_address_length(), self.exact_count())),
+        }
+    }
+
+    /// Addresses/second assumed for this search, and where it comes from: `--rate`, the rate
+    /// measured by an earlier run with the same settings, or the conservative built-in speed
+    pub fn assumed_rate(&self) -> (f64, &'static str) {
+        if let Some(rate) = self.rate.filter(|r| r.is_finite() && *r > 0.0) {
+            return (rate, "set by --rate");
+        }
+        match crate::rate_cache::load_rate(self.word_count(), self.addresses_per_seed) {
+            Some(rate) => (rate, "measured on this machine"),
+            None => (6_000.0, "built-in conservative speed"),
+        }
+    }
+
+    /// Refuses a search expected to outlast `--max-expected-days`, unless it is forced
+    /// or bounded by `--timeout`.
+    pub fn check_difficulty_budget(&self) -> Result<(), String> {
+        if self.force || self.timeout.is_some() {
+            return Ok(());
+        }
+        let (rate, source) = self.assumed_rate();
+        let addresses = estimator::expected_search_addresses(&self.estimated_patterns(), self.num, self.balanced, self.shape, crate::utils::p2pk_address_length(), self.exact_count());
+        let seconds = addresses / rate;
+        if seconds > self.max_expected_days * 86400.0 {
+            return Err(format!(
+                "This search is expected to take about {} at {} addresses/s ({}), over the {}-day limit. \
+                 Shorten the patterns, set --timeout, or pass --force to start anyway",
+                estimator::format_time(seconds),
+                crate::utils::group_digits(rate.round() as u64),
+                source,
+                self.max_expected_days
+            ));
+        }
+        Ok(())
+    }
+
+    /// Non-fatal problems with the arguments, printed before the search starts
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.addresses_per_seed > LARGE_ADDRESSES_PER_SEED {
+            warnings.push(format!(
+                "{} addresses per seed is unusually high; every seed derives all of them, which slows the search",
+                self.addresses_per_seed
+            ));
+        }
+        if let Some(max) = self.max_results.filter(|&max| max < self.num) {
+            warnings.push(format!("--max-results {} is below --num {}; the search stops after {} matches", max, self.num, max));
+        }
+        if self.tui && !cfg!(feature = "tui") {
+            warnings.push("This build has no terminal UI (build with --features tui); using plain output".to_string());
+        } else if self.tui && !std::io::stdout().is_terminal() {
+            warnings.push("--tui needs a terminal; using plain output".to_string());
+        }
+        warnings
+    }
+
+    /// Whether the search should run in the terminal UI: requested, built in, and on a terminal
+    pub fn use_tui(&self) -> bool {
+        self.tui && cfg!(feature = "tui") && std::io::stdout().is_terminal()
+    }
+
+    /// Parses the patterns into `(mode, pattern, explicit)` entries.
+    /// Patterns without a `start:`/`end:`/`any:` prefix use the global `--start`/`--end` mode.
+    pub fn pattern_specs(&self) -> Vec<(MatchMode, String, bool)> {
+        let default_mode = MatchMode::from_flags(self.start, self.end);
+        self.patterns
+            .iter()
+            .map(|p| parse_pattern_spec(p, default_mode))
+            .collect()
+    }
+
+    /// Occurrences `--exact-count` requires of anywhere patterns, if given
+    pub fn exact_count(&self) -> Option<usize> {
+        self.exact_count.map(usize::from)
     }
 
     /// Creates a new PatternMatcher based on the provided CLI arguments.
     pub fn create_matcher(&self) -> PatternMatcher {
-        PatternMatcher::new(
-            self.patterns.clone(),
-            self.case_sensitive,
-            self.start,
-            self.end,
-        )
+        let mut matcher = PatternMatcher::with_options(self.pattern_specs(), self.case_sensitive, self.shape);
+        matcher.set_end_before_checksum(self.end_before_checksum);
+        if self.clean {
+            matcher.set_excluded_chars(&self.clean_chars);
+        }
+        if self.dictionary {
+            matcher.set_dictionary(self.dictionary_min_len);
+        }
+        if let Some((len, class)) = self.class_run {
+            matcher.set_class_run(len, class);
+        }
+        if let Some(count) = self.exact_count() {
+            matcher.set_exact_count(count);
+        }
+        matcher
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::try_parse_from(["ergo-vanitygen", "--no-gui", "-p", "abc"].iter().chain(args)).unwrap()
+    }
+
+    #[test]
+    fn relative_output_paths_go_under_the_output_dir() {
+        let args = parse(&["--output-dir", "out", "--jsonl", "matches.jsonl", "--seed-sequence", "ab", "--manifest", "/tmp/bounty.json"]);
+        assert_eq!(args.jsonl_path(), Some(PathBuf::from("out/matches.jsonl")));
+        assert_eq!(args.manifest_path(), Some(PathBuf::from("/tmp/bounty.json")));
+        let args = parse(&["--seed-sequence", "ab", "--manifest", "bounty.json"]);
+        assert_eq!(args.manifest_path(), Some(PathBuf::from("bounty.json")));
+    }
+
+    #[test]
+    fn difficulty_budget_names_the_assumed_rate() {
+        let err = parse(&["--rate", "1", "-n", "100", "--max-expected-days", "1"]).check_difficulty_budget().unwrap_err();
+        assert!(err.contains("at 1 addresses/s (set by --rate), over the 1-day limit"), "{}", err);
+        assert!(parse(&["--rate", "1000000", "--max-expected-days", "1"]).check_difficulty_budget().is_ok());
+    }
+
+    #[test]
+    fn addresses_per_seed_must_be_positive_and_warns_when_large() {
+        assert_eq!(parse(&["-a", "0"]).validate().unwrap_err(), "--addresses-per-seed must be at least 1");
+        assert!(parse(&["-a", "1000"]).validate().is_ok());
+        assert!(parse(&["-a", "1000"]).warnings().is_empty());
+        let large = parse(&["-a", "1001"]);
+        assert!(large.validate().is_ok());
+        assert!(large.warnings()[0].starts_with("1001 addresses per seed is unusually high"));
+    }
+
+    #[test]
+    fn max_results_must_be_positive_and_warns_below_num() {
+        assert_eq!(parse(&["--max-results", "0"]).validate().unwrap_err(), "--max-results must be at least 1");
+        assert!(parse(&["--max-results", "5", "-n", "5"]).warnings().is_empty());
+        let capped = parse(&["--max-results", "2", "-n", "5"]);
+        assert!(capped.validate().is_ok());
+        assert_eq!(capped.warnings(), ["--max-results 2 is below --num 5; the search stops after 2 matches"]);
     }
 }
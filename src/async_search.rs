@@ -0,0 +1,94 @@
+//! Async wrapper around the search for embedding in `tokio` applications.
+//!
+//! The CPU-heavy work still runs on the processor's own Rayon pool; the async runtime
+//! only hosts a blocking task that waits for the search and a channel that carries matches.
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::Stream;
+use crate::address_processor::{AddressProcessor, MatchResult};
+use crate::matcher::PatternMatcher;
+
+/// Stream of matches from a running search. Dropping it cancels the search.
+pub struct MatchStream {
+    inner: UnboundedReceiverStream<MatchResult>,
+    processor: Arc<AddressProcessor>,
+    finished: Arc<AtomicBool>,
+}
+
+impl Stream for MatchStream {
+    type Item = MatchResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<MatchResult>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+impl Drop for MatchStream {
+    fn drop(&mut self) {
+        if self.finished.load(Ordering::SeqCst) {
+            return;
+        }
+        // cancel() waits for the progress thread, so keep it off the async runtime
+        let processor = Arc::clone(&self.processor);
+        std::thread::spawn(move || processor.cancel());
+    }
+}
+
+/// Starts a search on a blocking task and returns a stream of its matches.
+/// The stream ends when the search finishes (enough matches, timeout or cancellation).
+/// Must be called from within a tokio runtime. The processor's result callback is
+/// replaced for the duration of the search.
+pub fn search_stream(
+    processor: Arc<AddressProcessor>,
+    matcher: PatternMatcher,
+    word_count: usize,
+    num_results: usize,
+    balanced: bool,
+    addresses_per_seed: u32,
+) -> MatchStream {
+    let (tx, rx) = mpsc::unbounded_channel();
+    processor.set_result_callback(move |mnemonic, address, pattern, position, word_count, checked| {
+        // A closed channel means the stream was dropped; the drop handler cancels the search
+        let _ = tx.send((mnemonic.to_string(), address.to_string(), pattern.to_string(), position, word_count, checked));
+    });
+
+    let search = Arc::clone(&processor);
+    let finished = Arc::new(AtomicBool::new(false));
+    let search_finished = Arc::clone(&finished);
+    tokio::task::spawn_blocking(move || {
+        search.find_matches(matcher, word_count, num_results, balanced, addresses_per_seed);
+        search_finished.store(true, Ordering::SeqCst);
+        // Release the sender so the stream ends
+        search.set_result_callback(|_, _, _, _, _, _| {});
+    });
+
+    MatchStream {
+        inner: UnboundedReceiverStream::new(rx),
+        processor,
+        finished,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    #[test]
+    fn stream_yields_every_match_then_ends() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let processor = Arc::new(AddressProcessor::new());
+        processor.set_quiet(true);
+        processor.set_simulate(true);
+        let matcher = PatternMatcher::new(vec!["e".to_string()], false, false, false);
+
+        let matches: Vec<MatchResult> = runtime.block_on(async { search_stream(processor, matcher, 12, 3, false, 1).collect().await });
+        assert_eq!(matches.len(), 3);
+        assert!(matches.iter().all(|(_, address, pattern, ..)| pattern == "e" && address.to_lowercase().contains('e')));
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
+use crate::utils::{self, derive_addresses, generate_secure_mnemonic, AddressFormat, EntropySource, ERGO_COIN_TYPE};
+
+/// Minimum steady-state measuring time per batch size
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+
+/// File (in the home directory) where the last recommendation is stored
+const RECOMMENDATION_FILE: &str = ".ergo-vanitygen-autotune.json";
+
+/// Throughput measured for one batch size: (batch size, addresses/second)
+pub type TuneResult = (usize, f64);
+
+/// Generates one batch of `batch_size` seeds in parallel, returning the addresses derived
+fn run_batch(batch_size: usize, word_count: usize, addresses_per_seed: u32) -> usize {
+    (0..batch_size)
+        .into_par_iter()
+        .map(|_| {
+            let (secure_seed, _) = generate_secure_mnemonic(word_count, EntropySource::Csprng);
+            derive_addresses(secure_seed.as_str(), addresses_per_seed, AddressFormat::default(), ERGO_COIN_TYPE).count()
+        })
+        .sum()
+}
+
+/// Measures steady-state throughput for one batch size: one warm-up batch, then
+/// whole batches until at least `MEASURE_TIME` has passed.
+fn measure(batch_size: usize, word_count: usize, addresses_per_seed: u32) -> f64 {
+    run_batch(batch_size, word_count, addresses_per_seed);
+
+    let start = Instant::now();
+    let mut addresses = 0;
+    while start.elapsed() < MEASURE_TIME {
+        addresses += run_batch(batch_size, word_count, addresses_per_seed);
+    }
+    addresses as f64 / start.elapsed().as_secs_f64().max(f64::EPSILON)
+}
+
+/// Batch sizes swept between `min` and `max`, doubling each step
+fn candidate_sizes(min: usize, max: usize) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    let mut size = min.max(1);
+    while size < max {
+        sizes.push(size);
+        size *= 2;
+    }
+    sizes.push(max.max(1));
+    sizes
+}
+
+/// Sweeps batch sizes between `min` and `max`, prints the throughput of each and
+/// returns the fastest.
+pub fn run_and_print(min: usize, max: usize, word_count: usize, addresses_per_seed: u32) -> usize {
+    println!("Batch Size Auto-Tune");
+    println!("====================");
+    println!(
+        "Sweeping batch sizes {}..={} ({} addresses per seed, ~{}s each)\n",
+        min, max, addresses_per_seed, MEASURE_TIME.as_secs()
+    );
+
+    let results: Vec<TuneResult> = candidate_sizes(min, max)
+        .into_iter()
+        .map(|size| {
+            let rate = measure(size, word_count, addresses_per_seed);
+            println!("{:>8} seeds/batch {:>12.0} addresses/s", size, rate);
+            (size, rate)
+        })
+        .collect();
+
+    let (best, rate) = results
+        .iter()
+        .copied()
+        .fold((min, 0.0), |best, r| if r.1 > best.1 { r } else { best });
+    println!("\nBest batch size: {} ({:.0} addresses/s)", best, rate);
+    best
+}
+
+fn recommendation_path() -> Option<PathBuf> {
+    utils::home_file(RECOMMENDATION_FILE)
+}
+
+/// Stores the tuned batch size for these search settings so later runs can reuse it
+pub fn save_recommendation(batch_size: usize, word_count: usize, addresses_per_seed: u32) {
+    let Some(path) = recommendation_path() else {
+        return;
+    };
+    let data = serde_json::json!({
+        "batch_size": batch_size,
+        "word_count": word_count,
+        "addresses_per_seed": addresses_per_seed,
+    });
+    match std::fs::write(&path, data.to_string()) {
+        Ok(()) => println!("Saved recommendation to {}", path.display()),
+        Err(e) => log::warn!("Could not save auto-tune recommendation to {}: {}", path.display(), e),
+    }
+}
+
+/// Loads a stored batch size if it was tuned for the same word count and addresses per seed
+pub fn load_recommendation(word_count: usize, addresses_per_seed: u32) -> Option<usize> {
+    let data = std::fs::read_to_string(recommendation_path()?).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&data).ok()?;
+    if value["word_count"].as_u64()? as usize != word_count
+        || value["addresses_per_seed"].as_u64()? as u32 != addresses_per_seed
+    {
+        return None;
+    }
+    value["batch_size"].as_u64().map(|n| n as usize).filter(|&n| n > 0)
+}
@@ -0,0 +1,116 @@
+use std::time::Instant;
+use rand::Rng;
+use rayon::prelude::*;
+use crate::matcher::{MatchMode, PatternMatcher};
+use crate::utils::{derive_addresses, generate_secure_mnemonic, generate_simulated_addresses, AddressFormat, EntropySource, ERGO_COIN_TYPE};
+
+/// Seed lengths compared by the benchmark
+const WORD_LENGTHS: [usize; 3] = [12, 15, 24];
+
+/// Pattern counts compared by the matcher benchmark
+const MATCHER_PATTERN_COUNTS: [usize; 4] = [1, 10, 100, 1000];
+
+/// Length of the random patterns used by the matcher benchmark
+const MATCHER_PATTERN_LEN: usize = 5;
+
+/// Simulated addresses checked per pattern count by the matcher benchmark
+const MATCHER_ADDRESSES: u32 = 200_000;
+
+/// Throughput measured for one seed length: (word count, seeds/second, addresses/second)
+pub type BenchmarkResult = (usize, f64, f64);
+
+/// Generates `seeds` seeds of the given length in parallel and derives
+/// `addresses_per_seed` addresses from each, returning the measured rates.
+pub fn benchmark_word_length(word_count: usize, seeds: usize, addresses_per_seed: u32) -> BenchmarkResult {
+    let start = Instant::now();
+    let addresses: usize = (0..seeds)
+        .into_par_iter()
+        .map(|_| {
+            let (secure_seed, _) = generate_secure_mnemonic(word_count, EntropySource::Csprng);
+            derive_addresses(secure_seed.as_str(), addresses_per_seed, AddressFormat::default(), ERGO_COIN_TYPE).count()
+        })
+        .sum();
+    let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+    (word_count, seeds as f64 / elapsed, addresses as f64 / elapsed)
+}
+
+/// Runs the benchmark for 12, 15 and 24-word seeds, prints a comparison table and
+/// returns the measured rates.
+pub fn run_and_print(seeds: usize, addresses_per_seed: u32) -> Vec<BenchmarkResult> {
+    println!("Word Length Benchmark");
+    println!("=====================");
+    println!("{} seeds per length, {} addresses per seed\n", seeds, addresses_per_seed);
+
+    let results: Vec<BenchmarkResult> = WORD_LENGTHS
+        .iter()
+        .map(|&wc| {
+            println!("Benchmarking {}-word seeds...", wc);
+            benchmark_word_length(wc, seeds, addresses_per_seed)
+        })
+        .collect();
+
+    // Compare every length against the slowest to show the relative speedup
+    let baseline = results
+        .iter()
+        .map(|&(_, seed_rate, _)| seed_rate)
+        .fold(f64::INFINITY, f64::min);
+
+    println!("\n{:<8} {:>12} {:>14} {:>10}", "Words", "Seeds/s", "Addresses/s", "Relative");
+    for &(wc, seed_rate, addr_rate) in &results {
+        println!(
+            "{:<8} {:>12.0} {:>14.0} {:>9.2}x",
+            wc, seed_rate, addr_rate, seed_rate / baseline
+        );
+    }
+    results
+}
+
+/// Checks `addresses` simulated addresses against `pattern_count` random anywhere patterns
+/// and returns the addresses matched per second. Key derivation is left out, so this
+/// measures the matcher alone.
+pub fn benchmark_matcher(pattern_count: usize, addresses: u32) -> f64 {
+    let format = AddressFormat::default();
+    let charset = format.charset().as_bytes();
+    let mut rng = rand::thread_rng();
+    let specs = (0..pattern_count)
+        .map(|_| {
+            let pattern: String = (0..MATCHER_PATTERN_LEN)
+                .map(|_| charset[rng.gen_range(0..charset.len())] as char)
+                .collect();
+            (MatchMode::Anywhere, pattern, false)
+        })
+        .collect();
+    let matcher = PatternMatcher::with_modes(specs, false);
+    let candidates = generate_simulated_addresses(addresses, format);
+
+    let start = Instant::now();
+    let matched = candidates
+        .par_iter()
+        .filter(|info| matcher.is_match(&info.address).is_some())
+        .count();
+    let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+    log::debug!("{} of {} addresses matched {} patterns", matched, addresses, pattern_count);
+    addresses as f64 / elapsed
+}
+
+/// Runs the matcher benchmark for 1 to 1,000 patterns and prints a comparison table.
+pub fn run_matcher_and_print() {
+    let addresses = MATCHER_ADDRESSES;
+    println!("Matcher Benchmark");
+    println!("=================");
+    println!(
+        "{} simulated addresses, random {}-character anywhere patterns\n",
+        addresses, MATCHER_PATTERN_LEN
+    );
+
+    let results: Vec<(usize, f64)> = MATCHER_PATTERN_COUNTS
+        .iter()
+        .map(|&count| (count, benchmark_matcher(count, addresses)))
+        .collect();
+    let baseline = results[0].1;
+
+    println!("{:<10} {:>14} {:>10}", "Patterns", "Addresses/s", "Relative");
+    for &(count, rate) in &results {
+        println!("{:<10} {:>14.0} {:>9.2}x", count, rate, rate / baseline);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::crypto;
+
+/// Cargo features compiled into this binary
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "gui") { features.push("gui"); }
+    if cfg!(feature = "clipboard") { features.push("clipboard"); }
+    if cfg!(feature = "tui") { features.push("tui"); }
+    if cfg!(feature = "hw_accel") { features.push("hw_accel"); }
+    if cfg!(feature = "profile") { features.push("profile"); }
+    features
+}
+
+/// Prints version, dependency and CPU details useful for bug reports
+pub fn print_build_info(json: bool) {
+    let features = enabled_features();
+    let cpu_features = crypto::detect_cpu_features().names();
+
+    if json {
+        let info = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "ergo_lib_version": env!("ERGO_LIB_VERSION"),
+            "target": env!("BUILD_TARGET"),
+            "profile": env!("BUILD_PROFILE"),
+            "features": features,
+            "cpu_features": cpu_features,
+        });
+        println!("{}", serde_json::to_string_pretty(&info).unwrap_or_default());
+        return;
+    }
+
+    let list = |items: &[&str]| if items.is_empty() { "none".to_string() } else { items.join(", ") };
+    println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    println!("ergo-lib:     {}", env!("ERGO_LIB_VERSION"));
+    println!("Target:       {} ({})", env!("BUILD_TARGET"), env!("BUILD_PROFILE"));
+    println!("Features:     {}", list(&features));
+    println!("CPU features: {}", list(&cpu_features));
+}
@@ -29,6 +29,17 @@ impl CpuFeatures {
         }
     }
     
+    /// Names of the detected SIMD features, e.g. ["SSE2", "AVX2"]
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut available = Vec::new();
+        if self.sse2 { available.push("SSE2"); }
+        if self.sse4_1 { available.push("SSE4.1"); }
+        if self.avx { available.push("AVX"); }
+        if self.avx2 { available.push("AVX2"); }
+        if self.avx512f { available.push("AVX-512F"); }
+        available
+    }
+
     /// Returns a multiplier for batch sizes that's optimal for the current CPU
     pub fn batch_size_multiplier(&self) -> usize {
         let base = self.optimal_batch_size();
@@ -91,23 +102,21 @@ impl AccelContext {
     /// Log detected features
     pub fn log_features(&self) {
         if self.use_hw_accel {
-            let features = &self.features;
-            let mut available = Vec::new();
-            
-            if features.sse2 { available.push("SSE2"); }
-            if features.sse4_1 { available.push("SSE4.1"); }
-            if features.avx { available.push("AVX"); }
-            if features.avx2 { available.push("AVX2"); }
-            if features.avx512f { available.push("AVX-512F"); }
-            
+            let available = self.features.names();
             if !available.is_empty() {
-                println!("Using hardware acceleration with: {}", available.join(", "));
-                println!("Optimal batch size: {}", self.get_optimal_batch_size());
+                log::info!("Using hardware acceleration with: {}", available.join(", "));
+                log::info!("Optimal batch size: {}", self.get_optimal_batch_size());
             }
         }
     }
 }
 
+impl Default for AccelContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // This singleton ensures we only detect CPU features once
 lazy_static::lazy_static! {
     pub static ref ACCEL_CONTEXT: AccelContext = {
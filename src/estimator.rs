@@ -1,4 +1,10 @@
+use crate::matcher::{parse_shape, CharClass, MatchMode, ShapeClass, CHECKSUM_CHARS};
+use crate::utils::{p2pk_start_chars, AddressFormat};
+
 /// Structure representing the estimated effort for a given pattern.
+/// Times are in seconds at 6,000 (`time_at_min`) and 12,000 (`time_at_max`) addresses/second;
+/// impossible patterns serialize their infinite figures as null.
+#[derive(serde::Serialize)]
 pub struct PatternEstimate {
     pub attempts_needed: f64,
     pub time_at_min: f64,
@@ -7,26 +13,38 @@ pub struct PatternEstimate {
     pub invalid_chars: Vec<char>,
 }
 
+/// Attempt counts above this are treated as infeasible in practice
+/// (over 3,000 years at 10,000 addresses/second).
+pub const INFEASIBLE_ATTEMPTS: f64 = 1e15;
+
+/// Searches expected to take longer than this many days need `--force` (or confirmation in the GUI)
+pub const DEFAULT_MAX_EXPECTED_DAYS: f64 = 30.0;
+
+impl PatternEstimate {
+    /// Whether the pattern is possible in principle but hopeless in practice.
+    pub fn is_infeasible(&self) -> bool {
+        !self.has_invalid_chars && self.attempts_needed > INFEASIBLE_ATTEMPTS
+    }
+}
+
 /// Checks if a character is valid in the Base58 alphabet
+/// Excluded: 0 (zero), O (uppercase o), I (uppercase i), l (lowercase L)
 pub fn is_base58_char(c: char) -> bool {
-    // Base58 alphabet: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz
-    // Excluded: 0 (zero), O (uppercase o), I (uppercase i), l (lowercase L)
-    match c {
-        '0' | 'O' | 'I' | 'l' => false,
-        '1'..='9' | 'A'..='H' | 'J'..='N' | 'P'..='Z' | 'a'..='k' | 'm'..='z' => true,
-        _ => false,
-    }
+    AddressFormat::Base58.is_valid_char(c)
 }
 
 /// Estimates the number of attempts and time required to find an address matching the given pattern.
 ///
 /// # Parameters
 /// - `pattern`: The address pattern to search for.
-/// - `is_start`: If true, the pattern is assumed to match at the beginning (after the initial character).
+/// - `mode`: Where the pattern must appear. Prefixes and suffixes of equal length differ in
+///   difficulty: the character after the leading '9' is always one of e-i, so a valid prefix is
+///   more likely than a suffix, whose characters are all effectively random.
+/// - `address_length`: Length of the encoded addresses being searched (see `utils::p2pk_address_length`).
 ///
 /// # Returns
 /// A `PatternEstimate` with the adjusted number of attempts needed and time estimates at two speeds.
-pub fn estimate_pattern(pattern: &str, is_start: bool) -> PatternEstimate {
+pub fn estimate_pattern(pattern: &str, mode: MatchMode, address_length: usize) -> PatternEstimate {
     // Check for invalid Base58 characters
     let mut invalid_chars = Vec::new();
     for c in pattern.chars() {
@@ -51,17 +69,24 @@ pub fn estimate_pattern(pattern: &str, is_start: bool) -> PatternEstimate {
     let pattern_length = pattern.len() as f64;
 
     // Calculate the base number of attempts based on the matching location.
-    let attempts = if is_start {
-        // For start patterns: second character must be one of [e,f,g,h,i] (5 possibilities)
-        // followed by characters from a Base58 alphabet (58 possibilities each).
-        5.0 * 58.0f64.powf(pattern_length - 1.0)
-    } else {
-        // For end/anywhere patterns:
-        // Each character has 58 possibilities and there are multiple starting positions
-        // in an average ~40-character address.
-        let avg_addr_length = 40.0;
-        let positions = avg_addr_length - pattern_length + 1.0;
-        58.0f64.powf(pattern_length) / positions
+    let attempts = match mode {
+        // For start patterns: second character must be one of the start characters
+        // (e, f, g, h or i: 5 possibilities) followed by characters from a Base58 alphabet
+        // (58 possibilities each).
+        MatchMode::Start => p2pk_start_chars().len() as f64 * 58.0f64.powf(pattern_length - 1.0),
+        // For end patterns: a single position where every character has 58 possibilities.
+        MatchMode::End => 58.0f64.powf(pattern_length),
+        // For anywhere patterns: there are multiple starting positions in the fixed-length address.
+        MatchMode::Anywhere => {
+            let positions = (address_length as f64 - pattern_length + 1.0).max(1.0);
+            58.0f64.powf(pattern_length) / positions
+        }
+        // For checksum patterns: the few positions within the checksum tail, each character
+        // uniform over 58 because the checksum can't be steered.
+        MatchMode::Checksum => {
+            let positions = (CHECKSUM_CHARS as f64 - pattern_length + 1.0).max(1.0);
+            58.0f64.powf(pattern_length) / positions
+        }
     };
 
     // Apply a 20% safety margin.
@@ -80,6 +105,163 @@ pub fn estimate_pattern(pattern: &str, is_start: bool) -> PatternEstimate {
     }
 }
 
+/// Expected number of addresses to check to find `num_results` matches for `patterns` given
+/// as `(pattern, mode)`. A balanced search waits for every pattern's share, so the hardest
+/// pattern dominates; otherwise any match counts and the match rates add up.
+pub fn expected_search_addresses(patterns: &[(String, MatchMode)], num_results: usize, balanced: bool, shape: bool, address_length: usize, exact_count: Option<usize>) -> f64 {
+    let attempts: Vec<f64> = patterns
+        .iter()
+        .map(|(pattern, mode)| estimate(pattern, *mode, shape, address_length, exact_count).attempts_needed)
+        .collect();
+    if attempts.is_empty() {
+        return 0.0;
+    }
+
+    if balanced {
+        let quota = num_results.div_ceil(attempts.len()) as f64;
+        attempts.iter().fold(0.0, |hardest, a| hardest.max(quota * a))
+    } else {
+        let matches_per_address: f64 = attempts.iter().map(|a| 1.0 / a).sum();
+        num_results as f64 / matches_per_address
+    }
+}
+
+/// Expected time in seconds for `expected_search_addresses` at the conservative speed
+pub fn expected_search_seconds(patterns: &[(String, MatchMode)], num_results: usize, balanced: bool, shape: bool, address_length: usize, exact_count: Option<usize>) -> f64 {
+    expected_search_addresses(patterns, num_results, balanced, shape, address_length, exact_count) / 6_000.0
+}
+
+/// Estimates the effort for a `--shape` template: `L` positions accept any of the 49 Base58
+/// letters, `D` positions any of the 9 digits, and literals only themselves.
+pub fn estimate_shape(template: &str, mode: MatchMode, address_length: usize) -> PatternEstimate {
+    let mut invalid_chars = Vec::new();
+    for c in template.chars() {
+        if c != 'L' && c != 'D' && !is_base58_char(c) && !invalid_chars.contains(&c) {
+            invalid_chars.push(c);
+        }
+    }
+    if !invalid_chars.is_empty() {
+        return PatternEstimate {
+            attempts_needed: f64::INFINITY,
+            time_at_min: f64::INFINITY,
+            time_at_max: f64::INFINITY,
+            has_invalid_chars: true,
+            invalid_chars,
+        };
+    }
+
+    // Expected tries per position: 58 over the number of characters the class accepts
+    let classes = parse_shape(template, true);
+    let is_start = mode == MatchMode::Start;
+    // The second address character is always one of the start characters
+    let start_chars = p2pk_start_chars();
+    let start_tries = |accepted: usize| match accepted {
+        0 => f64::INFINITY,
+        accepted => start_chars.len() as f64 / accepted as f64,
+    };
+    let mut attempts: f64 = classes
+        .iter()
+        .enumerate()
+        .map(|(i, class)| match (is_start && i == 0, class) {
+            (true, ShapeClass::Letter) => start_tries(start_chars.iter().filter(|c| c.is_ascii_alphabetic()).count()),
+            (true, ShapeClass::Digit) => start_tries(start_chars.iter().filter(|c| c.is_ascii_digit()).count()),
+            (true, ShapeClass::Literal(_)) => start_chars.len() as f64,
+            (false, ShapeClass::Letter) => 58.0 / 49.0,
+            (false, ShapeClass::Digit) => 58.0 / 9.0,
+            (false, ShapeClass::Literal(_)) => 58.0,
+        })
+        .product();
+    if mode == MatchMode::Anywhere {
+        attempts /= (address_length as f64 - classes.len() as f64 + 1.0).max(1.0);
+    } else if mode == MatchMode::Checksum {
+        attempts /= (CHECKSUM_CHARS as f64 - classes.len() as f64 + 1.0).max(1.0);
+    }
+
+    let adjusted_attempts = attempts * 1.2;
+    PatternEstimate {
+        attempts_needed: adjusted_attempts,
+        time_at_min: adjusted_attempts / 6_000.0,
+        time_at_max: adjusted_attempts / 12_000.0,
+        has_invalid_chars: false,
+        invalid_chars: Vec::new(),
+    }
+}
+
+/// Estimates a `--class-run`: each of the `len` characters must fall in `class`, which holds
+/// only a fraction of the alphabet (9 of 58 characters for digits).
+pub fn estimate_class_run(len: usize, class: CharClass, format: AddressFormat) -> PatternEstimate {
+    let odds = format.charset().len() as f64 / class.size(format) as f64;
+    let adjusted_attempts = odds.powi(len as i32) * 1.2;
+    PatternEstimate {
+        attempts_needed: adjusted_attempts,
+        time_at_min: adjusted_attempts / 6_000.0,
+        time_at_max: adjusted_attempts / 12_000.0,
+        has_invalid_chars: false,
+        invalid_chars: Vec::new(),
+    }
+}
+
+/// Estimates a pattern, treating it as a shape template when `shape` is set. With
+/// `exact_count`, anywhere patterns must occur exactly that many times (see `estimate_exact_count`).
+pub fn estimate(pattern: &str, mode: MatchMode, shape: bool, address_length: usize, exact_count: Option<usize>) -> PatternEstimate {
+    let estimate = if shape {
+        estimate_shape(pattern, mode, address_length)
+    } else {
+        estimate_pattern(pattern, mode, address_length)
+    };
+    match exact_count {
+        Some(count) if mode == MatchMode::Anywhere => estimate_exact_count(estimate, pattern.len(), count, address_length),
+        _ => estimate,
+    }
+}
+
+/// Adjusts an anywhere estimate so the pattern must occur exactly `count` times rather than at
+/// least once. Each of the possible positions matches independently with the per-position
+/// probability behind `estimate`, so the count follows a binomial distribution. Exactly once
+/// is slightly harder than at least once, and every extra occurrence costs roughly another
+/// factor of the at-least-once attempts.
+pub fn estimate_exact_count(estimate: PatternEstimate, pattern_len: usize, count: usize, address_length: usize) -> PatternEstimate {
+    if !estimate.attempts_needed.is_finite() {
+        return estimate;
+    }
+    let positions = address_length.saturating_sub(pattern_len) + 1;
+    if count > positions {
+        return PatternEstimate { attempts_needed: f64::INFINITY, time_at_min: f64::INFINITY, time_at_max: f64::INFINITY, ..estimate };
+    }
+    // Undo the safety margin and spread the at-least-once chance over the positions
+    let p = (1.2 / estimate.attempts_needed / positions as f64).min(1.0);
+    let ln_choose: f64 = (0..count).map(|i| ((positions - i) as f64 / (i + 1) as f64).ln()).sum();
+    let ln_exact = ln_choose + count as f64 * p.ln() + (positions - count) as f64 * (-p).ln_1p();
+    let adjusted_attempts = 1.2 / ln_exact.exp();
+    PatternEstimate {
+        attempts_needed: adjusted_attempts,
+        time_at_min: adjusted_attempts / 6_000.0,
+        time_at_max: adjusted_attempts / 12_000.0,
+        ..estimate
+    }
+}
+
+/// Attempts giving a `target_prob` chance of at least one match when each attempt matches
+/// with probability `1 / expected_attempts`: the smallest n with 1 - (1 - p)^n >= target.
+fn attempts_for_probability(expected_attempts: f64, target_prob: f64) -> f64 {
+    if !expected_attempts.is_finite() {
+        return f64::INFINITY;
+    }
+    if expected_attempts <= 1.0 {
+        return 1.0;
+    }
+    ((1.0 - target_prob).ln() / (-1.0 / expected_attempts).ln_1p()).ceil()
+}
+
+/// Number of addresses to check (seeds, at one address per seed) for a `target_prob` chance of
+/// finding at least one match, treating the pattern as a shape template when `shape` is set and
+/// requiring exactly `exact_count` occurrences when given. Infinite when the pattern contains
+/// invalid characters.
+pub fn seeds_for_probability(pattern: &str, mode: MatchMode, shape: bool, exact_count: Option<usize>, target_prob: f64) -> f64 {
+    let estimate = estimate(pattern, mode, shape, crate::utils::p2pk_address_length(), exact_count);
+    attempts_for_probability(estimate.attempts_needed, target_prob)
+}
+
 /// Converts a duration in seconds into a human-readable string.
 pub fn format_time(seconds: f64) -> String {
     if seconds.is_infinite() {
@@ -99,29 +281,116 @@ pub fn format_time(seconds: f64) -> String {
 
 /// Prints the estimated number of attempts and time required to find a matching address.
 ///
-/// This displays the pattern, the estimated attempts needed, and the time estimates for two different speeds.
-pub fn print_estimate(pattern: &str, is_start: bool) {
-    let estimate = estimate_pattern(pattern, is_start);
+/// This displays the pattern, the estimated attempts needed, and the time estimates at `rate`
+/// addresses/second, or at two conservative speeds when no rate is known. With `confidence`,
+/// it also shows the attempts and time for that chance of at least one match.
+pub fn print_estimate(pattern: &str, mode: MatchMode, shape: bool, rate: Option<f64>, confidence: Option<f64>, exact_count: Option<usize>) {
+    let estimate = estimate(pattern, mode, shape, crate::utils::p2pk_address_length(), exact_count);
 
-    println!("\nPattern: \"{}\"", pattern);
+    println!("\nPattern: \"{}\" ({})", pattern, mode.prefix());
+    if let Some(count) = exact_count.filter(|_| mode == MatchMode::Anywhere) {
+        println!("Occurrences required: exactly {}", count);
+    }
 
     if estimate.has_invalid_chars {
         println!("WARNING: Pattern contains invalid Base58 characters:");
         println!("  Invalid characters: {}", estimate.invalid_chars.iter().collect::<String>());
         println!("  Valid characters: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
         println!("  This pattern is IMPOSSIBLE to find in a valid Ergo address.");
+    } else if estimate.is_infeasible() {
+        println!("Estimated attempts needed: {:.3e}", estimate.attempts_needed);
+        println!("WARNING: This pattern is infeasible in practice; shorten it.");
     } else {
         println!("Estimated attempts needed: {:.0}", estimate.attempts_needed);
         println!("Estimated time to find:");
-        println!("  At 6,000 addr/s: {}", format_time(estimate.time_at_min));
-        println!("  At 12,000 addr/s: {}", format_time(estimate.time_at_max));
+        match rate {
+            Some(rate) => println!(
+                "  At {} addr/s: {}",
+                crate::utils::group_digits(rate.round() as u64),
+                format_time(estimate.attempts_needed / rate)
+            ),
+            None => {
+                println!("  At 6,000 addr/s: {}", format_time(estimate.time_at_min));
+                println!("  At 12,000 addr/s: {}", format_time(estimate.time_at_max));
+            }
+        }
+        if let Some(target) = confidence {
+            let attempts = attempts_for_probability(estimate.attempts_needed, target);
+            println!("For a {:.0}% chance of at least one match:", target * 100.0);
+            println!("  Addresses to check: {:.0}", attempts);
+            match rate {
+                Some(rate) => println!("  Time: {}", format_time(attempts / rate)),
+                None => println!(
+                    "  Time: {} at 6,000 addr/s, {} at 12,000 addr/s",
+                    format_time(attempts / 6_000.0),
+                    format_time(attempts / 12_000.0)
+                ),
+            }
+        }
+    }
+}
+
+/// JSON form of the `--estimate` report for tooling: a `patterns` array holding each
+/// `PatternEstimate` with its pattern and mode (plus the time at `rate` and the attempts for
+/// a `confidence` chance of a match, when given), and a `combined` estimate for finding
+/// `num_results` matches over all patterns.
+#[allow(clippy::too_many_arguments)]
+pub fn estimates_json(
+    patterns: &[(String, MatchMode)],
+    num_results: usize,
+    balanced: bool,
+    shape: bool,
+    rate: Option<f64>,
+    confidence: Option<f64>,
+    exact_count: Option<usize>,
+) -> serde_json::Value {
+    let address_length = crate::utils::p2pk_address_length();
+    let per_pattern: Vec<serde_json::Value> = patterns
+        .iter()
+        .map(|(pattern, mode)| {
+            let estimate = estimate(pattern, *mode, shape, address_length, exact_count);
+            let mut json = serde_json::to_value(&estimate).unwrap_or_default();
+            json["pattern"] = serde_json::Value::from(pattern.as_str());
+            json["mode"] = serde_json::Value::from(mode.prefix());
+            json["infeasible"] = serde_json::Value::from(estimate.is_infeasible());
+            if let Some(count) = exact_count.filter(|_| *mode == MatchMode::Anywhere) {
+                json["exact_count"] = serde_json::Value::from(count);
+            }
+            if let Some(rate) = rate {
+                json["time_at_rate"] = serde_json::Value::from(estimate.attempts_needed / rate);
+            }
+            if let Some(target) = confidence {
+                json["attempts_at_confidence"] = serde_json::Value::from(attempts_for_probability(estimate.attempts_needed, target));
+            }
+            json
+        })
+        .collect();
+
+    let attempts = expected_search_addresses(patterns, num_results, balanced, shape, address_length, exact_count);
+    let mut combined = serde_json::json!({
+        "num_results": num_results,
+        "balanced": balanced,
+        "attempts_needed": attempts,
+        "time_at_min": attempts / 6_000.0,
+        "time_at_max": attempts / 12_000.0,
+    });
+    if let Some(rate) = rate {
+        combined["time_at_rate"] = serde_json::Value::from(attempts / rate);
     }
+    let mut json = serde_json::json!({ "patterns": per_pattern, "combined": combined });
+    if let Some(rate) = rate {
+        json["rate"] = serde_json::Value::from(rate);
+    }
+    if let Some(target) = confidence {
+        json["confidence"] = serde_json::Value::from(target);
+    }
+    json
 }
 
 /// Wrapper function that prints the estimate and difficulty header
 ///
 /// This is a convenience function called from main.rs
-pub fn estimate_and_print(pattern: &str, is_start: bool) {
+pub fn estimate_and_print(pattern: &str, mode: MatchMode, shape: bool, rate: Option<f64>, confidence: Option<f64>, exact_count: Option<usize>) {
     // Print header only for the first pattern
     static HEADER_PRINTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
     if !HEADER_PRINTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
@@ -129,5 +398,88 @@ pub fn estimate_and_print(pattern: &str, is_start: bool) {
         println!("====================");
     }
 
-    print_estimate(pattern, is_start);
+    print_estimate(pattern, mode, shape, rate, confidence, exact_count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= expected * 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn anywhere_patterns_count_the_positions_of_the_address_length() {
+        // 20% safety margin over 58^3 spread across the 49 positions of a 51-character address
+        let estimate = estimate_pattern("abc", MatchMode::Anywhere, 51);
+        assert_close(estimate.attempts_needed, 1.2 * 58.0f64.powi(3) / 49.0);
+        // A longer address offers more positions, so the pattern takes fewer attempts
+        assert!(estimate_pattern("abc", MatchMode::Anywhere, 60).attempts_needed < estimate.attempts_needed);
+        // The position count never drops below one, even for a pattern as long as the address
+        assert_close(estimate_pattern("abc", MatchMode::Anywhere, 2).attempts_needed, 1.2 * 58.0f64.powi(3));
+    }
+
+    #[test]
+    fn prefixes_are_easier_than_suffixes_of_the_same_length() {
+        // After the leading '9' only the start characters can follow, so a valid prefix's first
+        // character is 1 in 5 rather than 1 in 58
+        let start = estimate_pattern("eab", MatchMode::Start, 51).attempts_needed;
+        let end = estimate_pattern("eab", MatchMode::End, 51).attempts_needed;
+        assert_close(start, 1.2 * p2pk_start_chars().len() as f64 * 58.0f64.powi(2));
+        assert_close(end, 1.2 * 58.0f64.powi(3));
+        assert!(start < end);
+    }
+
+    #[test]
+    fn attempts_for_probability_is_the_smallest_sufficient_count() {
+        let chance = |n: f64| 1.0 - (1.0 - 1.0 / 1_000.0f64).powf(n);
+        for target in [0.5, 0.9, 0.99] {
+            let n = attempts_for_probability(1_000.0, target);
+            assert!(chance(n) >= target && chance(n - 1.0) < target, "{} attempts for {}", n, target);
+        }
+        assert_eq!(attempts_for_probability(1_000.0, 0.5), 693.0);
+        assert_eq!(attempts_for_probability(0.5, 0.99), 1.0);
+        assert!(attempts_for_probability(f64::INFINITY, 0.5).is_infinite());
+    }
+
+    #[test]
+    fn seeds_for_probability_follows_the_pattern_estimate() {
+        let expected = estimate_pattern("abc", MatchMode::End, crate::utils::p2pk_address_length()).attempts_needed;
+        let even = seeds_for_probability("abc", MatchMode::End, false, None, 0.5);
+        // An even chance takes about ln 2 of the expected attempts
+        assert!(even > expected * 0.69 && even < expected * 0.7);
+        assert!(seeds_for_probability("abc", MatchMode::End, false, None, 0.9) > expected);
+        assert!(seeds_for_probability("ab0", MatchMode::End, false, None, 0.5).is_infinite());
+        // As a shape, "LD" is a letter then a digit rather than the literal characters
+        let shape = estimate_shape("LD", MatchMode::End, crate::utils::p2pk_address_length()).attempts_needed;
+        assert_eq!(seeds_for_probability("LD", MatchMode::End, true, None, 0.5), attempts_for_probability(shape, 0.5));
+    }
+
+    #[test]
+    fn exact_counts_follow_the_binomial_distribution() {
+        // "ab" fits 50 positions of a 51-character address, each matching with p = 1/58^2
+        let p = 1.0 / 58.0f64.powi(2);
+        let exactly_once = 50.0 * p * (1.0 - p).powi(49);
+        let exactly_twice = 1225.0 * p.powi(2) * (1.0 - p).powi(48);
+        let at_least_once = || estimate_pattern("ab", MatchMode::Anywhere, 51);
+        assert_close(estimate_exact_count(at_least_once(), 2, 1, 51).attempts_needed, 1.2 / exactly_once);
+        assert_close(estimate_exact_count(at_least_once(), 2, 2, 51).attempts_needed, 1.2 / exactly_twice);
+        assert!(estimate_exact_count(at_least_once(), 2, 51, 51).attempts_needed.is_infinite());
+
+        let length = crate::utils::p2pk_address_length();
+        let twice = estimate("ab", MatchMode::Anywhere, false, length, Some(2)).attempts_needed;
+        assert_eq!(seeds_for_probability("ab", MatchMode::Anywhere, false, Some(2), 0.5), attempts_for_probability(twice, 0.5));
+    }
+
+    #[test]
+    fn shapes_multiply_the_odds_of_each_class() {
+        // A letter is 49 of 58 characters and a digit 9 of 58
+        assert_close(estimate_shape("LD", MatchMode::End, 51).attempts_needed, 1.2 * (58.0 / 49.0) * (58.0 / 9.0));
+        assert_close(estimate_shape("LDx", MatchMode::End, 51).attempts_needed, 1.2 * (58.0 / 49.0) * (58.0 / 9.0) * 58.0);
+        // Every start character is a letter, so a leading L is free after the '9'
+        assert_close(estimate_shape("LD", MatchMode::Start, 51).attempts_needed, 1.2 * 58.0 / 9.0);
+        assert!(estimate_shape("D", MatchMode::Start, 51).attempts_needed.is_infinite());
+        assert!(estimate_shape("L0", MatchMode::End, 51).has_invalid_chars);
+    }
 }
@@ -0,0 +1,105 @@
+use std::io::Write;
+use std::sync::Mutex;
+use crate::metadata::ResultMetadata;
+use crate::utils::{derivation_path_string, ERGO_COIN_TYPE};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(windows)]
+use std::fs::{File, OpenOptions};
+
+/// What match events carry besides the match itself
+#[derive(Clone, Default)]
+pub struct EventOptions {
+    /// Include the seed phrase (sensitive!)
+    pub include_seed: bool,
+    /// Add `ResultMetadata` under "metadata"
+    pub include_metadata: bool,
+    /// Search label (`--label`), already sanitized
+    pub label: Option<String>,
+    /// Non-standard BIP44 coin type (`--coin-type`); None for Ergo's
+    pub coin_type: Option<u32>,
+}
+
+/// JSON object describing one match, as sent to the event socket and the JSONL output.
+pub fn match_event(
+    options: &EventOptions,
+    mnemonic: &str,
+    address: &str,
+    pattern: &str,
+    position: u32,
+    word_count: usize,
+    addresses_checked: usize,
+) -> serde_json::Value {
+    let mut event = serde_json::json!({
+        "event": "match",
+        "address": address,
+        "pattern": pattern,
+        "position": position,
+        "derivation_path": derivation_path_string(options.coin_type.unwrap_or(ERGO_COIN_TYPE), 0, position),
+        "word_count": word_count,
+        "addresses_checked": addresses_checked,
+    });
+    if let Some(label) = &options.label {
+        event["label"] = serde_json::Value::from(label.as_str());
+    }
+    if let Some(coin_type) = options.coin_type {
+        event["coin_type"] = serde_json::Value::from(coin_type);
+    }
+    if options.include_seed {
+        event["mnemonic"] = serde_json::Value::from(mnemonic);
+    }
+    if options.include_metadata {
+        event["metadata"] = ResultMetadata::new(word_count, position, options.coin_type.unwrap_or(ERGO_COIN_TYPE))
+            .with_label(options.label.clone())
+            .to_json();
+    }
+    event
+}
+
+/// Writes match events as JSON lines to a local Unix domain socket
+/// (or a named pipe on Windows) so a daemon can consume them.
+pub struct EventSocket {
+    #[cfg(unix)]
+    stream: Mutex<Option<UnixStream>>,
+    #[cfg(windows)]
+    stream: Mutex<Option<File>>,
+    options: EventOptions,
+}
+
+impl EventSocket {
+    /// Connects to the socket (or opens the named pipe) at `path`.
+    pub fn connect(path: &str, options: EventOptions) -> Result<Self, String> {
+        #[cfg(unix)]
+        let stream = UnixStream::connect(path)
+            .map_err(|e| format!("Failed to connect to event socket '{}': {}", path, e))?;
+
+        #[cfg(windows)]
+        let stream = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open event pipe '{}': {}", path, e))?;
+
+        Ok(Self {
+            stream: Mutex::new(Some(stream)),
+            options,
+        })
+    }
+
+    /// Sends one match as a single JSON line.
+    /// If the consumer has disconnected, the socket is dropped and further events are ignored
+    /// so the search itself keeps running.
+    pub fn send_match(&self, mnemonic: &str, address: &str, pattern: &str, position: u32, word_count: usize, addresses_checked: usize) {
+        let mut guard = self.stream.lock().unwrap();
+        let Some(stream) = guard.as_mut() else {
+            return;
+        };
+
+        let event = match_event(&self.options, mnemonic, address, pattern, position, word_count, addresses_checked);
+        let line = format!("{}\n", event);
+        if let Err(e) = stream.write_all(line.as_bytes()).and_then(|_| stream.flush()) {
+            log::warn!("Event socket consumer disconnected ({}), no further events will be sent", e);
+            *guard = None;
+        }
+    }
+}
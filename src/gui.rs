@@ -1,20 +1,56 @@
 use egui::{Color32, RichText, ScrollArea, TextEdit, Ui};
 use eframe::{App, Frame, NativeOptions};
 use poll_promise::Promise;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use chrono::Local;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use rfd::FileDialog;
+use zeroize::Zeroizing;
 
-use crate::address_processor::{AddressProcessor, MatchResult};
-use crate::matcher::PatternMatcher;
-use crate::paper_wallet::PaperWalletInfo;
+use crate::address_processor::{AddressProcessor, MatchResult, PatternTally};
+use crate::matcher::{parse_pattern_spec, MatchMode, PatternMatcher};
+use crate::metadata::{sanitize_label, ResultMetadata};
+use crate::paper_wallet::{decode_qr_image, EncryptionOptions, PaperWalletInfo, DEFAULT_QR_CARDS, MAX_QR_CARDS};
 use crate::estimator;
+use crate::progress::{expected_progress_label, Throttle};
+use crate::utils::{
+    derivation_path_string, describe_chars, format_count, group_digits, home_file, is_p2pk_start_char, p2pk_start_chars,
+    generate_addresses, seed_entropy_hex, verify_mnemonic, AddressFormat, AddressInfo, SeedOutputFormat, WordMix, ERGO_COIN_TYPE,
+    SIMULATED_SEED, UNIFORM_WORD_MIX,
+};
 
 const MAX_LOG_ENTRIES: usize = 100;
 
+/// File (in the home directory) where GUI display preferences are stored
+const SETTINGS_FILE: &str = ".ergo-vanitygen-gui.json";
+
+/// Characters kept at the start and end of a shortened address
+const SHORT_ADDRESS_HEAD: usize = 12;
+const SHORT_ADDRESS_TAIL: usize = 8;
+
+/// Color of the matched part of an address in the results
+const MATCH_HIGHLIGHT: Color32 = Color32::from_rgb(229, 192, 123);
+/// How long a copied seed stays on the clipboard before it is overwritten
+const CLIPBOARD_CLEAR_AFTER: Duration = Duration::from_secs(60);
+/// Text written over a copied seed (egui ignores empty clipboard writes)
+const CLIPBOARD_PLACEHOLDER: &str = " ";
+
+/// Addresses the seed preview derives by default, and at most
+const SEED_PREVIEW_DEFAULT: u32 = 5;
+const SEED_PREVIEW_MAX: u32 = 50;
+
+/// Dots shown for every word when masking hides word lengths
+const LENGTH_ONLY_DOTS: usize = 5;
+
+/// How often a balanced search logs each pattern's progress and ETA
+const PATTERN_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum width of the seed details under an expanded results table row
+const RESULT_DETAIL_WIDTH: f32 = 420.0;
+
 /// Tabs for the GUI.
 #[derive(PartialEq, Copy, Clone)]
 enum Tab {
@@ -23,6 +59,69 @@ enum Tab {
     Log,
 }
 
+/// How much of each word a masked seed shows
+#[derive(PartialEq, Copy, Clone)]
+enum SeedMask {
+    /// Every character as a dot; word lengths still show
+    Full,
+    /// First and last character of each word
+    Partial,
+    /// The same number of dots for every word, hiding word lengths too
+    LengthOnly,
+}
+
+impl SeedMask {
+    const ALL: [SeedMask; 3] = [SeedMask::Full, SeedMask::Partial, SeedMask::LengthOnly];
+
+    fn label(self) -> &'static str {
+        match self {
+            SeedMask::Full => "All dots",
+            SeedMask::Partial => "First and last letter",
+            SeedMask::LengthOnly => "Hide word lengths",
+        }
+    }
+
+    /// Name stored in the settings file
+    fn key(self) -> &'static str {
+        match self {
+            SeedMask::Full => "full",
+            SeedMask::Partial => "partial",
+            SeedMask::LengthOnly => "length-only",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mask| mask.key() == key)
+    }
+
+    /// Masks each whitespace-separated word of `data`
+    fn apply(self, data: &str) -> String {
+        data.split_whitespace()
+            .map(|word| match self {
+                SeedMask::Full => "•".repeat(word.chars().count()),
+                SeedMask::LengthOnly => "•".repeat(LENGTH_ONLY_DOTS),
+                SeedMask::Partial if word.len() <= 2 => word.to_string(),
+                SeedMask::Partial => {
+                    // Show first character and last character, mask the rest
+                    let first = word.chars().next().unwrap();
+                    let last = word.chars().last().unwrap();
+                    format!("{}{}{}", first, "•".repeat(word.len() - 2), last)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Sortable columns of the results table
+#[derive(PartialEq, Copy, Clone)]
+enum ResultColumn {
+    Index,
+    Pattern,
+    Position,
+    WordCount,
+}
+
 /// Main application structure.
 pub struct VanityGenApp {
     // --- GUI State ---
@@ -34,27 +133,88 @@ pub struct VanityGenApp {
     fifteen_words: bool,
     twenty_four_words: bool,
     all_word_lengths: bool,
+    // Weights of 12, 15 and 24-word seeds with "All lengths" (persisted, see `save_settings`)
+    word_mix: WordMix,
     addresses_per_seed: u32,
     num_results: usize,
+    keep_going: bool,
+    prefer_low_index: bool,
     balanced: bool,
+    simulate: bool,
     current_tab: Tab,
 
+    // Show results as first 12 + last 8 characters (persisted, see `save_settings`)
+    short_addresses: bool,
+    // Show results as a sortable table instead of cards (persisted)
+    results_table: bool,
+    // Table sort column and direction (true = ascending), and the rows showing their seed
+    results_sort: (ResultColumn, bool),
+    expanded_results: HashSet<usize>,
+    // QR module grids of the seeds shown QR-only, encoded and verified once per result
+    seed_qr_grids: HashMap<String, Result<(usize, Vec<bool>), String>>,
+
     // Add security options
     mask_seed_phrases: bool,
+    seed_mask: SeedMask,
     show_security_warning: bool,
+    seed_output_format: SeedOutputFormat,
+    qr_only_seed: bool,
+    // When a copied seed will be wiped from the clipboard, if one is pending
+    clipboard_clear_at: Option<Instant>,
+    // Never touch the clipboard: copy actions save to a file instead. Locked on by --file-only
+    file_only: bool,
+    file_only_locked: bool,
 
     // Seed phrase unmasking
     show_unmasked_seed: bool,
     current_unmasked_seed: String,
 
+    // Paper wallet options dialog, open while a wallet is pending
+    pending_paper_wallet: Option<PaperWalletInfo>,
+    paper_wallet_encrypt: bool,
+    paper_wallet_password: Zeroizing<String>,
+    paper_wallet_password_confirm: Zeroizing<String>,
+    paper_wallet_hint: String,
+    paper_wallet_word_qrs: bool,
+    paper_wallet_metadata: bool,
+    paper_wallet_restore_checklist: bool,
+    paper_wallet_qr_cards: usize,
+    paper_wallet_verify_qr: bool,
+    // Seed preview dialog; the typed seed is cleared as soon as derivation starts
+    show_seed_preview: bool,
+    seed_preview_input: Zeroizing<String>,
+    seed_preview_count: u32,
+    seed_preview: Option<Promise<Result<Vec<AddressInfo>, String>>>,
+    // Name typed for the next search, and the sanitized label of the current results
+    search_label: String,
+    result_label: Option<String>,
+    // Directory the save dialog opens in (--output-dir), or the working directory
+    output_dir: Option<PathBuf>,
+
+    // Expected duration of a search awaiting confirmation because it exceeds the difficulty budget
+    confirm_long_search: Option<f64>,
+    // Addresses the running search is expected to need, for the expected progress bar
+    expected_addresses: Option<f64>,
+    // Result count when the Results tab was last shown automatically
+    last_result_count: usize,
+
     // --- Results and Statistics ---
     results: Arc<Mutex<Vec<MatchResult>>>,
+    // Length of `results`, kept by the result callback so the UI can read it every frame without locking
+    result_count: Arc<AtomicUsize>,
+    // Matcher of the last search, used to locate the matched part of each result
+    result_matcher: Option<PatternMatcher>,
     logs: VecDeque<String>,
     stats: Arc<Mutex<Option<(usize, usize, f64, f64, usize)>>>,
+    pattern_tally: Arc<Mutex<PatternTally>>,
+    // Patterns of the running search with the attempts each is expected to need per match
+    pattern_attempts: Vec<(String, f64)>,
+    // Log lines from the search thread, moved into `logs` every frame
+    search_log: Arc<Mutex<Vec<String>>>,
 
     // --- Processing State ---
     running: Arc<Mutex<bool>>,
-    promise: Option<Promise<()>>,
+    promise: Option<Promise<Result<(), String>>>,
     start_time: Option<Instant>,
     processor: Option<Arc<AddressProcessor>>,
 }
@@ -70,22 +230,63 @@ impl Default for VanityGenApp {
             fifteen_words: false,
             twenty_four_words: true,
             all_word_lengths: false,
+            word_mix: UNIFORM_WORD_MIX,
             addresses_per_seed: 1,
             num_results: 1,
+            keep_going: false,
+            prefer_low_index: false,
             balanced: false,
+            simulate: false,
             current_tab: Tab::Status,
 
             // Initialize security options
+            short_addresses: false,
+            results_table: false,
+            results_sort: (ResultColumn::Index, true),
+            expanded_results: HashSet::new(),
+            seed_qr_grids: HashMap::new(),
             mask_seed_phrases: true,
+            seed_mask: SeedMask::Partial,
             show_security_warning: true,
+            seed_output_format: SeedOutputFormat::Words,
+            qr_only_seed: false,
+            clipboard_clear_at: None,
+            file_only: false,
+            file_only_locked: false,
 
             // Seed phrase unmasking
             show_unmasked_seed: false,
             current_unmasked_seed: String::new(),
 
+            pending_paper_wallet: None,
+            confirm_long_search: None,
+            expected_addresses: None,
+            last_result_count: 0,
+            paper_wallet_encrypt: false,
+            paper_wallet_password: Zeroizing::new(String::new()),
+            paper_wallet_password_confirm: Zeroizing::new(String::new()),
+            paper_wallet_hint: String::new(),
+            paper_wallet_word_qrs: false,
+            paper_wallet_metadata: false,
+            paper_wallet_restore_checklist: false,
+            paper_wallet_qr_cards: DEFAULT_QR_CARDS,
+            paper_wallet_verify_qr: false,
+            show_seed_preview: false,
+            seed_preview_input: Zeroizing::new(String::new()),
+            seed_preview_count: SEED_PREVIEW_DEFAULT,
+            seed_preview: None,
+            search_label: String::new(),
+            result_label: None,
+            output_dir: None,
+
             results: Arc::new(Mutex::new(Vec::new())),
+            result_count: Arc::new(AtomicUsize::new(0)),
+            result_matcher: None,
             logs: VecDeque::with_capacity(MAX_LOG_ENTRIES),
             stats: Arc::new(Mutex::new(None)),
+            pattern_tally: Arc::new(Mutex::new(Vec::new())),
+            pattern_attempts: Vec::new(),
+            search_log: Arc::new(Mutex::new(Vec::new())),
             running: Arc::new(Mutex::new(false)),
             promise: None,
             start_time: None,
@@ -99,6 +300,14 @@ impl App for VanityGenApp {
         // Handle window close request
         if ctx.input(|i| i.viewport().close_requested()) {
             self.handle_window_close();
+            // Don't leave a copied seed behind on exit
+            if self.clipboard_clear_at.is_some() {
+                self.clear_clipboard(ctx);
+            }
+        }
+        if self.clipboard_clear_at.is_some_and(|at| Instant::now() >= at) {
+            self.clear_clipboard(ctx);
+            self.add_log("Clipboard cleared");
         }
         // Request frequent updates for smooth animations
         ctx.request_repaint_after(Duration::from_millis(10));
@@ -107,15 +316,16 @@ impl App for VanityGenApp {
         }
 
         // Auto-switch to Results tab when new matches are found
-        static LAST_LOGGED_COUNT: AtomicUsize = AtomicUsize::new(0);
-        let last_logged = LAST_LOGGED_COUNT.load(Ordering::Relaxed);
-        let result_count = self.results.lock().unwrap().len();
-        if result_count > last_logged {
+        let result_count = self.result_count.load(Ordering::Relaxed);
+        if result_count > self.last_result_count {
             ctx.request_repaint();
             self.current_tab = Tab::Results;
         }
-        if result_count > 0 {
-            LAST_LOGGED_COUNT.store(result_count, Ordering::Relaxed);
+        self.last_result_count = result_count;
+
+        let search_log = std::mem::take(&mut *self.search_log.lock().unwrap());
+        for line in search_log {
+            self.add_log(&line);
         }
 
         // Show unmasked seed phrase modal when requested
@@ -128,6 +338,24 @@ impl App for VanityGenApp {
                     ui.label(RichText::new("⚠️ SENSITIVE DATA - Keep Safe").strong().size(16.0).color(Color32::RED));
                     ui.separator();
 
+                    // Re-parse the phrase so users know it is well-formed before writing it down
+                    if self.current_unmasked_seed == SIMULATED_SEED {
+                        ui.colored_label(Color32::from_rgb(224, 108, 117), "Simulated result - this is not a real seed phrase");
+                    } else {
+                        match verify_mnemonic(&self.current_unmasked_seed) {
+                            Ok(()) => {
+                                ui.colored_label(Color32::LIGHT_GREEN, "✔ Valid BIP39 checksum");
+                            }
+                            Err(e) => {
+                                ui.label(RichText::new(format!("✖ INVALID SEED PHRASE: {}", e))
+                                    .strong().size(16.0).color(Color32::RED));
+                                ui.label(RichText::new("Do not use this seed. This indicates a generation bug - please report it.")
+                                    .color(Color32::RED));
+                            }
+                        }
+                    }
+                    ui.add_space(5.0);
+
                     let text_style = egui::TextStyle::Monospace;
                     let row_height = ui.text_style_height(&text_style) * 1.5;
                     let seed_words: Vec<&str> = self.current_unmasked_seed.split_whitespace().collect();
@@ -166,6 +394,17 @@ impl App for VanityGenApp {
                 });
         }
 
+        if self.pending_paper_wallet.is_some() {
+            self.show_paper_wallet_dialog(ctx);
+        }
+        if self.show_seed_preview {
+            self.show_seed_preview_dialog(ctx);
+        }
+        self.check_search_failure();
+        if self.confirm_long_search.is_some() {
+            self.show_long_search_dialog(ctx);
+        }
+
         // Left sidebar for settings and configuration
         egui::SidePanel::left("sidebar")
             .frame(egui::Frame::dark_canvas(&ctx.style()).inner_margin(10.0))
@@ -220,7 +459,8 @@ impl App for VanityGenApp {
                         });
                     }
                 }
-                ui.label("Comma-separated for multiple patterns");
+                ui.label("Comma-separated for multiple patterns")
+                    .on_hover_text("Prefix a pattern with start:, end: or any: to override the match type for it, e.g. start:efg, end:xyz");
 
                 // Add Base58 info with subtle coloring
                 ui.label(
@@ -233,6 +473,13 @@ impl App for VanityGenApp {
                     ui.label("These restrictions exist in the Ergo address format to prevent confusion between similar-looking characters.");
                 });
 
+                if ui.button("📷 Import from QR...")
+                    .on_hover_text("Load comma- or line-separated patterns from a QR code image (PNG or JPEG)")
+                    .clicked()
+                {
+                    self.import_patterns_from_qr();
+                }
+
                 ui.add_space(5.0);
 
                 ui.label("Match type:");
@@ -290,7 +537,7 @@ impl App for VanityGenApp {
                     self.all_word_lengths = false;
                 }
                 if ui.radio_value(&mut self.all_word_lengths, true, "All lengths")
-                    .on_hover_text("Randomly choose 12, 15, or 24 words")
+                    .on_hover_text("Randomly choose 12, 15, or 24 words, weighted by the mix below")
                     .clicked()
                 {
                     self.twelve_words = false;
@@ -300,6 +547,9 @@ impl App for VanityGenApp {
                 if !self.twelve_words && !self.fifteen_words && !self.twenty_four_words && !self.all_word_lengths {
                     self.twenty_four_words = true;
                 }
+                if self.all_word_lengths {
+                    self.render_word_mix(ui);
+                }
 
                 ui.add_space(10.0);
                 ui.horizontal(|ui| {
@@ -310,6 +560,8 @@ impl App for VanityGenApp {
                             .speed(0.1),
                     )
                     .on_hover_text("How many addresses are checked per seed phrase");
+                    ui.add_enabled(self.addresses_per_seed > 1, egui::Checkbox::new(&mut self.prefer_low_index, "Prefer low indices"))
+                        .on_hover_text("When several matches turn up at once, keep the ones at the lowest address index");
                 });
                 ui.horizontal(|ui| {
                     ui.label("Results to find:");
@@ -319,9 +571,18 @@ impl App for VanityGenApp {
                             .speed(0.1),
                     )
                     .on_hover_text("Number of matching addresses to find");
+                    ui.checkbox(&mut self.keep_going, "Unlimited")
+                        .on_hover_text("Keep searching after this many matches until stopped");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Label:");
+                    ui.add(egui::TextEdit::singleline(&mut self.search_label).hint_text("optional").desired_width(160.0))
+                        .on_hover_text("Name for this search, shown in the log and on paper wallets (letters, digits, - _ . only)");
                 });
                 ui.checkbox(&mut self.balanced, "Balanced matches")
                     .on_hover_text("Distribute matches evenly across patterns");
+                ui.checkbox(&mut self.simulate, "Simulate (testing only)")
+                    .on_hover_text("Match random strings instead of real addresses. Results are NOT real wallets");
 
                 ui.add_space(15.0);
                 let patterns: Vec<String> = self.input_patterns
@@ -333,8 +594,12 @@ impl App for VanityGenApp {
                     .on_hover_text("Estimate time needed for search")
                     .clicked()
                 {
-                    for pattern in &patterns {
-                        let estimate = estimator::estimate_pattern(pattern, self.start_match);
+                    let address_length = crate::utils::p2pk_address_length();
+                    let default_mode = MatchMode::from_flags(self.start_match, self.end_match);
+                    for spec in &patterns {
+                        let (mode, pattern, _) = parse_pattern_spec(spec, default_mode);
+                        let pattern = &pattern;
+                        let estimate = estimator::estimate_pattern(pattern, mode, address_length);
 
                         if estimate.has_invalid_chars {
                             self.add_log(&format!(
@@ -364,7 +629,13 @@ impl App for VanityGenApp {
                 if ui.add_enabled(can_start, egui::Button::new("Start Search").fill(Color32::from_rgb(0, 120, 0)))
                     .clicked()
                 {
-                    self.start_search();
+                    // Ask before starting a search that is not expected to finish in reasonable time
+                    let seconds = self.expected_search_seconds();
+                    if seconds > estimator::DEFAULT_MAX_EXPECTED_DAYS * 86400.0 {
+                        self.confirm_long_search = Some(seconds);
+                    } else {
+                        self.start_search();
+                    }
                 }
                 if ui.add_enabled(is_running, egui::Button::new("Stop Search").fill(Color32::from_rgb(180, 0, 0)))
                     .clicked()
@@ -389,15 +660,16 @@ impl App for VanityGenApp {
                         .rounding(8.0)
                     );
 
-                    if donate_button.clicked() {
-                        const DONATION_ADDRESS: &str = "9fMUoW2fVXzG8yBaGzaRNtWS8wcpNLJc6HCPrK6YFs6SkNDYryK";
-                        ui.output_mut(|o| o.copied_text = DONATION_ADDRESS.to_string());
+                    const DONATION_ADDRESS: &str = "9fMUoW2fVXzG8yBaGzaRNtWS8wcpNLJc6HCPrK6YFs6SkNDYryK";
+                    if donate_button.clicked()
+                        && self.copy_text(ui.ctx(), DONATION_ADDRESS, "Donation address", "ergo-vanitygen-donation-address.txt")
+                    {
                         self.add_log("Donation address copied to clipboard");
                     }
 
                     if donate_button.hovered() {
                         egui::show_tooltip(ui.ctx(), egui::Id::new("donation_tooltip"), |ui| {
-                            ui.label("Click to copy donation address");
+                            ui.label(if self.file_only { "Click to save donation address" } else { "Click to copy donation address" });
                             ui.label("9ergoFunMJ5MffMM31siayxK4juNGJ1qBQXukFJRy4jXVF4S66K");
                         });
                     }
@@ -427,7 +699,7 @@ impl App for VanityGenApp {
                 if tab_button(ui, "Status", self.current_tab == Tab::Status).clicked() {
                     self.current_tab = Tab::Status;
                 }
-                let results_count = self.results.lock().unwrap().len();
+                let results_count = self.result_count.load(Ordering::Relaxed);
                 let results_label = if results_count > 0 {
                     format!("Results ({})", results_count)
                 } else {
@@ -489,18 +761,35 @@ impl VanityGenApp {
             self.current_tab = Tab::Log;
 
             // Add specific messages based on error type
-            if self.start_match && patterns.iter().any(|p| {
+            let default_mode = MatchMode::from_flags(self.start_match, self.end_match);
+            if patterns.iter().any(|spec| {
+                let (mode, p, _) = parse_pattern_spec(spec, default_mode);
                 let first_char = p.chars().next().unwrap_or('_');
-                !['e', 'f', 'g', 'h', 'i'].contains(&first_char)
+                mode == MatchMode::Start && !is_p2pk_start_char(first_char, self.case_sensitive)
             }) {
-                self.add_log("Invalid start pattern: Ergo addresses can only start with e, f, g, h, or i");
+                self.add_log(&format!(
+                    "Invalid start pattern: Ergo addresses can only start with {}",
+                    describe_chars(p2pk_start_chars())
+                ));
                 self.add_log("Try 'Anywhere' or 'End' matching instead for this pattern");
             }
             return;
         }
+        for warning in matcher.warnings() {
+            self.add_log(&format!("Warning: {}", warning));
+        }
+        self.result_matcher = Some(matcher);
+        self.expected_addresses = Some(estimator::expected_search_addresses(
+            &self.estimated_patterns(), self.num_results, self.balanced, false, crate::utils::p2pk_address_length(), None
+        )).filter(|e| e.is_finite() && *e > 0.0);
 
+        if self.all_word_lengths && self.word_mix.iter().all(|&w| w == 0) {
+            self.add_log("Error: Give at least one seed length a weight in the mix");
+            self.current_tab = Tab::Log;
+            return;
+        }
         let word_count = if self.all_word_lengths {
-            0 // Use random seed length (12/15/24)
+            0 // Use random seed length (12/15/24), weighted by the mix
         } else if self.twelve_words {
             12
         } else if self.fifteen_words {
@@ -512,9 +801,13 @@ impl VanityGenApp {
         let end_match = self.end_match;
         let case_sensitive = self.case_sensitive;
         let patterns_clone = patterns.clone();
-        let addresses_per_seed = self.addresses_per_seed;
+        let addresses_per_seed = self.addresses_per_seed.max(1);
         let num_results = self.num_results;
         let balanced = self.balanced;
+        let simulate = self.simulate;
+        let keep_going = self.keep_going;
+        let prefer_low_index = self.prefer_low_index;
+        let qr_only_seed = self.qr_only_seed;
 
         self.start_time = Some(Instant::now());
         *self.running.lock().unwrap() = true;
@@ -532,20 +825,34 @@ impl VanityGenApp {
             Arc::new(AddressProcessor::new())
         };
         self.processor = Some(processor.clone());
+        processor.set_simulate(simulate);
+        processor.set_keep_going(keep_going);
+        processor.set_prefer_low_index(prefer_low_index);
+        processor.set_word_mix(self.word_mix);
+        processor.set_qr_only_seed(qr_only_seed);
 
         // Set up the callback for new matches.
         let results_for_callback = results.clone();
+        let result_count = self.result_count.clone();
         let logs_arc = Arc::new(Mutex::new(VecDeque::with_capacity(MAX_LOG_ENTRIES)));
         let logs_for_callback = logs_arc.clone();
-        processor.set_result_callback(move |mnemonic, address, pattern, position, word_count| {
+        // Seeds are logged as shown in the results: not at all when QR-only, masked when masking is on
+        let seed_mask = self.mask_seed_phrases.then_some(self.seed_mask);
+        processor.set_result_callback(move |mnemonic, address, pattern, position, word_count, checked| {
             results_for_callback.lock().unwrap().push((
-                mnemonic.to_string(), address.to_string(), pattern.to_string(), position, word_count
+                mnemonic.to_string(), address.to_string(), pattern.to_string(), position, word_count, checked
             ));
+            result_count.fetch_add(1, Ordering::Relaxed);
             let mut logs = logs_for_callback.lock().unwrap();
             logs.push_back(format!("Match for pattern '{}'!", pattern));
             logs.push_back(format!("Address: {}", address));
             logs.push_back(format!("Position: {}", position));
-            logs.push_back(format!("Seed ({}-word): {}", word_count, mnemonic));
+            logs.push_back(format!("Path: {}", derivation_path_string(ERGO_COIN_TYPE, 0, position)));
+            logs.push_back(format!("Found after ~{} addresses", format_count(checked)));
+            if !qr_only_seed {
+                let shown = seed_mask.map_or_else(|| mnemonic.to_string(), |mask| mask.apply(mnemonic));
+                logs.push_back(format!("Seed ({}-word): {}", word_count, shown));
+            }
             logs.push_back("---------------------------".to_string());
             while logs.len() > MAX_LOG_ENTRIES {
                 logs.pop_front();
@@ -567,37 +874,77 @@ impl VanityGenApp {
             "Using {}{}, checking {} addresses per seed",
             seed_type, seed_suffix, addresses_per_seed
         ));
+        self.result_label = sanitize_label(&self.search_label);
+        if let Some(label) = &self.result_label {
+            self.add_log(&format!("Search label: {}", label));
+        }
+        if simulate {
+            self.add_log("SIMULATION MODE: results are random strings, NOT real wallets");
+        }
 
         self.results.lock().unwrap().clear();
-        static LAST_LOGGED_COUNT: AtomicUsize = AtomicUsize::new(0);
-        LAST_LOGGED_COUNT.store(0, Ordering::Relaxed);
+        self.result_count.store(0, Ordering::Relaxed);
+        self.pattern_tally.lock().unwrap().clear();
+        let pattern_tally = self.pattern_tally.clone();
+        let address_length = crate::utils::p2pk_address_length();
+        self.pattern_attempts = self
+            .estimated_patterns()
+            .into_iter()
+            .map(|(pattern, mode)| {
+                let attempts = estimator::estimate_pattern(&pattern, mode, address_length).attempts_needed;
+                (pattern, attempts)
+            })
+            .collect();
+        // Per-pattern log lines only help spot the bottleneck of a balanced search
+        let pattern_log = (balanced && self.pattern_attempts.len() > 1).then(|| {
+            (self.pattern_attempts.clone(), self.pattern_target(), self.search_log.clone())
+        });
+        self.last_result_count = 0;
+        self.expanded_results.clear();
+        self.seed_qr_grids.clear();
 
         self.promise = Some(Promise::spawn_thread("address_search", move || {
             let matcher = PatternMatcher::new(patterns_clone.clone(), case_sensitive, start_match, end_match);
             let thread_count = processor.get_stats().4;
             let stats_clone = stats.clone();
-            let results_for_logging = results.clone();
-            let previously_found = Arc::new(AtomicUsize::new(0));
-
-            processor.set_progress_callback(move |seeds, addresses, seed_rate, addr_rate| {
-                static LAST_UPDATE: AtomicUsize = AtomicUsize::new(0);
-                let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as usize;
-                let last_update = LAST_UPDATE.load(Ordering::Relaxed);
-                if now - last_update > 100 {
+
+            let throttle = Throttle::new(Duration::from_millis(100));
+            let log_throttle = Throttle::new(PATTERN_LOG_INTERVAL);
+            processor.set_detailed_progress_callback(move |seeds, addresses, seed_rate, addr_rate, _found, tally| {
+                if throttle.ready() {
                     *stats_clone.lock().unwrap() = Some((seeds, addresses, seed_rate, addr_rate, thread_count));
-                    LAST_UPDATE.store(now, Ordering::Relaxed);
-                    let current_count = results_for_logging.lock().unwrap().len();
-                    let prev_count = previously_found.load(Ordering::Relaxed);
-                    if current_count > prev_count {
-                        previously_found.store(current_count, Ordering::Relaxed);
+                    *pattern_tally.lock().unwrap() = tally.to_vec();
+                }
+                if let Some((attempts, target, search_log)) = &pattern_log {
+                    if addr_rate > 0.0 && log_throttle.ready() {
+                        search_log.lock().unwrap().extend(
+                            pattern_progress(tally, attempts, *target, addr_rate)
+                                .into_iter()
+                                .map(|(pattern, count, eta)| {
+                                    format!("Pattern '{}': {}/{} found, {}", pattern, count, target, format_eta(eta))
+                                }),
+                        );
                     }
                 }
             });
 
-            let _matches = processor.find_matches(matcher, word_count, num_results, balanced, addresses_per_seed);
+            // A panicking worker must not leave the GUI looking busy forever
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                processor.find_matches(matcher, word_count, num_results, balanced, addresses_per_seed)
+            }));
+            if outcome.is_err() {
+                processor.cancel();
+            }
             let final_stats = processor.get_stats();
             *stats.lock().unwrap() = Some(final_stats);
             *running.lock().unwrap() = false;
+            outcome.map(|_| ()).map_err(|panic| {
+                panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown error".to_string())
+            })
         }));
 
         // Switch tab to Results if matches are found.
@@ -609,6 +956,20 @@ impl VanityGenApp {
         }
     }
 
+    /// Reports a search thread that died with a panic and drops its processor, whose
+    /// state can't be trusted afterwards; the next search starts with a fresh one.
+    fn check_search_failure(&mut self) {
+        let Some(Err(error)) = self.promise.as_ref().and_then(|p| p.ready()) else {
+            return;
+        };
+        let error = error.clone();
+        self.promise = None;
+        self.processor = None;
+        *self.running.lock().unwrap() = false;
+        self.add_log(&format!("Error: the search stopped unexpectedly: {}", error));
+        self.current_tab = Tab::Log;
+    }
+
     /// Stops the search.
     fn stop_search(&mut self) {
         *self.running.lock().unwrap() = false;
@@ -617,7 +978,6 @@ impl VanityGenApp {
 
         if let Some(processor) = processor_clone {
             processor.cancel();
-            std::thread::sleep(std::time::Duration::from_millis(50));
             processor.reset();
             self.add_log("Search cancelled");
         } else {
@@ -655,22 +1015,71 @@ impl VanityGenApp {
                     ui.end_row();
 
                     ui.label("Seeds checked:");
-                    ui.label(format!("{}", total_seeds));
+                    ui.label(group_digits(total_seeds as u64));
                     ui.end_row();
 
                     ui.label("Addresses checked:");
-                    ui.label(format!("{}", total_addresses));
+                    ui.label(group_digits(total_addresses as u64));
                     ui.end_row();
 
                     ui.label("Seed rate:");
-                    ui.label(RichText::new(format!("{:.0} seeds/second", seed_rate))
+                    ui.label(RichText::new(format!("{} seeds/second", group_digits(seed_rate.round() as u64)))
                         .color(if seed_rate > 0.0 { Color32::from_rgb(152, 195, 121) } else { Color32::LIGHT_GRAY }));
                     ui.end_row();
 
                     ui.label("Address rate:");
-                    ui.label(RichText::new(format!("{:.0} addresses/second", address_rate))
+                    ui.label(RichText::new(format!("{} addresses/second", group_digits(address_rate.round() as u64)))
                         .color(if address_rate > 0.0 { Color32::from_rgb(152, 195, 121) } else { Color32::LIGHT_GRAY }));
                     ui.end_row();
+
+                    if let Some(expected) = self.expected_addresses {
+                        ui.label("Expected progress:");
+                        ui.add(egui::ProgressBar::new((total_addresses as f64 / expected).min(1.0) as f32)
+                            .text(expected_progress_label(total_addresses, expected)))
+                            .on_hover_text("Addresses checked against the estimate. Matches are random, so this is a rough guide, not a guarantee");
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    /// Matches each pattern of the running search aims for: its balanced quota, or the total
+    /// number of results otherwise
+    fn pattern_target(&self) -> usize {
+        if self.balanced {
+            self.num_results.div_ceil(self.pattern_attempts.len().max(1))
+        } else {
+            self.num_results
+        }.max(1)
+    }
+
+    /// Shows one progress bar per pattern: matches found against that pattern's target.
+    /// A running balanced search also shows each pattern's ETA, so the bottleneck stands out.
+    fn show_pattern_tally(&self, ui: &mut Ui, tally: &[(String, usize)]) {
+        let target = self.pattern_target();
+        let address_rate = self.stats.lock().unwrap().map_or(0.0, |stats| stats.3);
+        let show_eta = self.balanced && *self.running.lock().unwrap();
+
+        let frame = egui::Frame::dark_canvas(&ui.ctx().style())
+            .rounding(egui::Rounding::same(6.0))
+            .inner_margin(12.0);
+        frame.show(ui, |ui| {
+            ui.heading("Matches per Pattern");
+            ui.add_space(8.0);
+            egui::Grid::new("pattern_tally_grid")
+                .num_columns(if show_eta { 3 } else { 2 })
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    for (pattern, count, eta) in pattern_progress(tally, &self.pattern_attempts, target, address_rate) {
+                        ui.label(&pattern);
+                        ui.add(egui::ProgressBar::new((count as f32 / target as f32).min(1.0))
+                            .text(format!("{} / {}", count, target)));
+                        if show_eta {
+                            ui.label(format_eta(eta))
+                                .on_hover_text("Expected time to fill this pattern at the current speed. Matches are random, so this is a rough guide");
+                        }
+                        ui.end_row();
+                    }
                 });
         });
     }
@@ -787,6 +1196,12 @@ impl VanityGenApp {
             }
             ui.add_space(12.0);
 
+            let tally = self.pattern_tally.lock().unwrap().clone();
+            if !tally.is_empty() {
+                self.show_pattern_tally(ui, &tally);
+                ui.add_space(12.0);
+            }
+
             frame.show(ui, |ui| {
                 ui.heading("Current Configuration");
                 ui.add_space(8.0);
@@ -817,14 +1232,21 @@ impl VanityGenApp {
                         ui.label(seed_type);
                         ui.end_row();
                         ui.label("Addresses per seed:");
-                        ui.label(self.addresses_per_seed.to_string());
+                        ui.label(if self.prefer_low_index && self.addresses_per_seed > 1 {
+                            format!("{} (lowest index preferred)", self.addresses_per_seed)
+                        } else {
+                            self.addresses_per_seed.to_string()
+                        });
                         ui.end_row();
                         ui.label("Results to find:");
-                        ui.label(self.num_results.to_string());
+                        ui.label(if self.keep_going { format!("{}+ (unlimited)", self.num_results) } else { self.num_results.to_string() });
                         ui.end_row();
                         ui.label("Balanced matching:");
                         ui.label(if self.balanced { "Yes" } else { "No" });
                         ui.end_row();
+                        ui.label("Simulation:");
+                        ui.label(if self.simulate { "Yes (not real wallets)" } else { "No" });
+                        ui.end_row();
                     });
             });
         });
@@ -839,10 +1261,68 @@ impl VanityGenApp {
         ui.horizontal(|ui| {
             ui.checkbox(&mut self.mask_seed_phrases, "Mask seed phrases")
                 .on_hover_text("Hide seed phrases for security");
+            let mask = self.seed_mask;
+            ui.add_enabled_ui(self.mask_seed_phrases, |ui| {
+                egui::ComboBox::from_id_source("seed_mask")
+                    .selected_text(self.seed_mask.label())
+                    .show_ui(ui, |ui| {
+                        for option in SeedMask::ALL {
+                            ui.selectable_value(&mut self.seed_mask, option, option.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text("How much of each word masked seeds show to someone looking over your shoulder; hiding word lengths reveals the least");
+            });
+            if self.seed_mask != mask {
+                self.save_settings();
+            }
 
             if ui.button("Security Tips").clicked() {
                 self.show_security_warning = true;
             }
+            if ui.button("🔎 Preview Seed Addresses")
+                .on_hover_text("Paste a seed phrase to see the addresses it derives, to check this tool agrees with your wallet")
+                .clicked()
+            {
+                self.show_seed_preview = true;
+            }
+
+            ui.label("Seed format:");
+            egui::ComboBox::from_id_source("seed_output_format")
+                .selected_text(match self.seed_output_format {
+                    SeedOutputFormat::Words => "Words",
+                    SeedOutputFormat::Hex => "Entropy hex",
+                    SeedOutputFormat::Both => "Both",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.seed_output_format, SeedOutputFormat::Words, "Words");
+                    ui.selectable_value(&mut self.seed_output_format, SeedOutputFormat::Hex, "Entropy hex");
+                    ui.selectable_value(&mut self.seed_output_format, SeedOutputFormat::Both, "Both");
+                })
+                .response
+                .on_hover_text("Show seeds as BIP39 words, raw entropy hex, or both");
+
+            ui.checkbox(&mut self.qr_only_seed, "QR-only seeds")
+                .on_hover_text("Never show seeds as text, only as a QR code. The QR code still contains the secret!");
+
+            ui.add_enabled(!self.file_only_locked, egui::Checkbox::new(&mut self.file_only, "File only"))
+                .on_hover_text("Never use the clipboard, which clipboard managers and other programs can read: copy buttons save to a file instead. For air-gapped machines (locked on by --file-only)");
+
+            ui.label("View:");
+            let table = self.results_table;
+            ui.selectable_value(&mut self.results_table, false, "Cards");
+            ui.selectable_value(&mut self.results_table, true, "Table")
+                .on_hover_text("One sortable row per match; expand a row to see its seed");
+            if self.results_table != table {
+                self.save_settings();
+            }
+
+            if ui.checkbox(&mut self.short_addresses, "Short addresses")
+                .on_hover_text("Show the first 12 and last 8 characters; hover for the full address. Copying always uses the full address")
+                .changed()
+            {
+                self.save_settings();
+            }
         });
 
         // Security warning popup
@@ -870,7 +1350,18 @@ impl VanityGenApp {
         }
 
         let results = self.results.lock().unwrap().clone();
-        ui.label(RichText::new(format!("Total matches found: {}", results.len())).strong());
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Total matches found: {}", results.len())).strong());
+            if let Some(at) = self.clipboard_clear_at {
+                let remaining = at.saturating_duration_since(Instant::now()).as_secs() + 1;
+                ui.label(RichText::new(format!("Clipboard will be cleared in {}s", remaining))
+                    .color(Color32::LIGHT_YELLOW));
+                if ui.small_button("Clear now").clicked() {
+                    self.clear_clipboard(ui.ctx());
+                    self.add_log("Clipboard cleared");
+                }
+            }
+        });
         ui.add_space(4.0);
         if results.is_empty() {
             let available_size = ui.available_size();
@@ -888,10 +1379,12 @@ impl VanityGenApp {
                         .color(Color32::LIGHT_GRAY).italics());
                 }
             });
+        } else if self.results_table {
+            self.show_results_table(ui, &results);
         } else {
             ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
                 for (i, result) in results.iter().enumerate() {
-                    let (mnemonic, address, pattern, position, word_count) = result;
+                    let (mnemonic, address, pattern, position, word_count, checked) = result;
                     let frame = egui::Frame::dark_canvas(&ui.ctx().style())
                         .stroke(egui::Stroke::new(1.0, Color32::from_gray(100)))
                         .inner_margin(10.0)
@@ -899,85 +1392,569 @@ impl VanityGenApp {
                         .rounding(8.0);
                     frame.show(ui, |ui| {
                         ui.colored_label(Color32::from_rgb(220, 220, 255), format!("Match #{}: Pattern \"{}\"", i + 1, pattern));
+                        if mnemonic == SIMULATED_SEED {
+                            ui.colored_label(Color32::from_rgb(224, 108, 117), "SIMULATED RESULT - NOT A REAL WALLET");
+                        }
                         ui.separator();
                         ui.horizontal(|ui| {
                             ui.strong("Address: ");
-                            ui.label(RichText::new(address).color(Color32::LIGHT_GREEN));
-                            if ui.small_button("📋 Copy").clicked() {
-                                ui.output_mut(|o| o.copied_text = address.clone());
-                                self.add_log("Address copied to clipboard");
-                            }
+                            self.result_address(ui, address, pattern);
                         });
                         ui.horizontal(|ui| {
                             ui.strong("Position: ");
                             ui.label(position.to_string());
+                            ui.label(RichText::new(format!("(found after ~{} addresses)", format_count(*checked)))
+                                .color(Color32::LIGHT_GRAY).italics());
                         });
                         ui.horizontal(|ui| {
-                            ui.strong(format!("Seed phrase ({}-word):", word_count));
+                            ui.strong("Path: ");
+                            ui.monospace(derivation_path_string(ERGO_COIN_TYPE, 0, *position));
                         });
+                        self.result_seed(ui, mnemonic, *word_count);
+                        self.result_seed_actions(ui, result);
+                    });
+                    ui.add_space(5.0);
+                }
+            });
+        }
+    }
+
+    /// Results as a sortable table, one row per match; expanding a row reveals its seed and actions
+    fn show_results_table(&mut self, ui: &mut Ui, results: &[MatchResult]) {
+        let (column, ascending) = self.results_sort;
+        let mut order: Vec<usize> = (0..results.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (ra, rb) = (&results[a], &results[b]);
+            let ordering = match column {
+                ResultColumn::Index => a.cmp(&b),
+                ResultColumn::Pattern => ra.2.cmp(&rb.2),
+                ResultColumn::Position => ra.3.cmp(&rb.3),
+                ResultColumn::WordCount => ra.4.cmp(&rb.4),
+            };
+            // Ties stay in the order they were found
+            if ascending { ordering } else { ordering.reverse() }.then(a.cmp(&b))
+        });
+
+        ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("results_table")
+                .striped(true)
+                .num_columns(6)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    self.sort_header(ui, ResultColumn::Index, "#");
+                    ui.strong("Address");
+                    self.sort_header(ui, ResultColumn::Pattern, "Pattern");
+                    self.sort_header(ui, ResultColumn::Position, "Position");
+                    self.sort_header(ui, ResultColumn::WordCount, "Words");
+                    ui.end_row();
 
-                        // Show masked or unmasked seed phrase based on user preference
-                        if self.mask_seed_phrases {
-                            ui.horizontal(|ui| {
-                                let masked_seed = self.mask_sensitive_data(mnemonic);
-                                ui.label(RichText::new(masked_seed).monospace().color(Color32::LIGHT_YELLOW));
+                    for i in order {
+                        let result = &results[i];
+                        let (mnemonic, address, pattern, position, word_count, _) = result;
+                        let expanded = self.expanded_results.contains(&i);
+                        if ui.small_button(if expanded { "▼" } else { "▶" })
+                            .on_hover_text(if expanded { "Hide seed" } else { "Show seed and actions" })
+                            .clicked()
+                        {
+                            if expanded {
+                                self.expanded_results.remove(&i);
+                            } else {
+                                self.expanded_results.insert(i);
+                            }
+                        }
+                        ui.label((i + 1).to_string());
+                        ui.horizontal(|ui| self.result_address(ui, address, pattern));
+                        ui.label(pattern.as_str());
+                        ui.label(position.to_string());
+                        ui.label(word_count.to_string());
+                        ui.end_row();
 
-                                if ui.small_button("👁 Show").clicked() {
-                                    // Set the current seed to be shown in a modal
-                                    self.show_unmasked_seed = true;
-                                    self.current_unmasked_seed = mnemonic.clone();
+                        if expanded {
+                            ui.label("");
+                            ui.label("");
+                            ui.vertical(|ui| {
+                                // Keep long seeds from widening the address column
+                                ui.set_max_width(RESULT_DETAIL_WIDTH);
+                                if mnemonic == SIMULATED_SEED {
+                                    ui.colored_label(Color32::from_rgb(224, 108, 117), "SIMULATED RESULT - NOT A REAL WALLET");
                                 }
+                                self.result_seed(ui, mnemonic, *word_count);
+                                self.result_seed_actions(ui, result);
                             });
-                        } else {
-                            ui.horizontal_wrapped(|ui| {
-                                ui.label(RichText::new(mnemonic).monospace().color(Color32::LIGHT_YELLOW));
-                            });
+                            ui.end_row();
                         }
+                    }
+                });
+        });
+    }
 
-                        ui.horizontal(|ui| {
-                            if ui.small_button("📋 Copy seed").clicked() {
-                                ui.output_mut(|o| o.copied_text = mnemonic.clone());
-                                self.add_log("Seed phrase copied to clipboard - clear clipboard when done!");
-
-                                // Prompt user to clear clipboard after 60 seconds
-                                let ctx = ui.ctx().clone();
-                                std::thread::spawn(move || {
-                                    std::thread::sleep(std::time::Duration::from_secs(60));
-                                    ctx.request_repaint(); // Request repaint to show the notification
-                                });
-                            }
+    /// Column header that sorts the results table, toggling the direction when clicked again
+    fn sort_header(&mut self, ui: &mut Ui, column: ResultColumn, title: &str) {
+        let (current, ascending) = self.results_sort;
+        let text = if current == column {
+            format!("{} {}", title, if ascending { "↑" } else { "↓" })
+        } else {
+            title.to_string()
+        };
+        if ui.selectable_label(current == column, RichText::new(text).strong())
+            .on_hover_text("Sort by this column")
+            .clicked()
+        {
+            self.results_sort = (column, current != column || !ascending);
+        }
+    }
 
-                            // Add paper wallet generation button
-                            if ui.small_button("📄 Generate Paper Wallet").clicked() {
-                                let paper_wallet_info = PaperWalletInfo {
-                                    address: address.clone(),
-                                    mnemonic: mnemonic.clone(),
-                                    word_count: *word_count,
-                                    position: *position,
-                                };
+    /// Address (highlighted, possibly shortened) with copy buttons for it and its matched part
+    fn result_address(&mut self, ui: &mut Ui, address: &str, pattern: &str) {
+        let span = self.result_matcher.as_ref().and_then(|m| m.match_span(address, pattern));
+        let shown = ui.label(highlighted_address(address, span, self.short_addresses));
+        if self.short_addresses {
+            shown.on_hover_text(address);
+        }
+        let copy_label = match (self.file_only, self.short_addresses) {
+            (true, _) => "💾 Save",
+            (false, true) => "📋 Copy full",
+            (false, false) => "📋 Copy",
+        };
+        if ui.small_button(copy_label).clicked()
+            && self.copy_text(ui.ctx(), address, "Address", &format!("ergo-address-{}.txt", &address[..10]))
+        {
+            // The seed is no longer on the clipboard, so there is nothing to wipe
+            self.clipboard_clear_at = None;
+            self.add_log("Address copied to clipboard");
+        }
+        if let Some((offset, len)) = span {
+            let portion = &address[offset..offset + len];
+            let chars = format!("characters {}-{}", offset + 1, offset + len);
+            let (copy_label, verb) = if self.file_only { ("💾 Save match", "Save") } else { ("📋 Copy match", "Copy") };
+            if ui.small_button(copy_label).on_hover_text(format!("{} \"{}\" ({})", verb, portion, chars)).clicked()
+                && self.copy_text(ui.ctx(), portion, "Matched portion", &format!("ergo-match-{}.txt", portion))
+            {
+                self.clipboard_clear_at = None;
+                self.add_log(&format!("Matched portion \"{}\" ({}) copied to clipboard", portion, chars));
+            }
+        }
+    }
 
-                                self.generate_paper_wallet(paper_wallet_info);
-                            }
+    /// Seed of a result as words, entropy hex or QR code, following the display options
+    fn result_seed(&mut self, ui: &mut Ui, mnemonic: &str, word_count: usize) {
+        if self.qr_only_seed {
+            ui.horizontal(|ui| {
+                ui.strong(format!("Seed phrase ({}-word):", word_count));
+                ui.label(RichText::new("QR only - the code contains the secret")
+                    .color(Color32::LIGHT_GRAY).italics());
+            });
+            let grid = self
+                .seed_qr_grids
+                .entry(mnemonic.to_string())
+                .or_insert_with(|| crate::paper_wallet::qr_modules(mnemonic));
+            show_seed_qr(ui, grid);
+        } else if self.seed_output_format.shows_words() {
+            ui.horizontal(|ui| {
+                ui.strong(format!("Seed phrase ({}-word):", word_count));
+            });
+
+            // Show masked or unmasked seed phrase based on user preference
+            if self.mask_seed_phrases {
+                ui.horizontal(|ui| {
+                    let masked_seed = self.mask_sensitive_data(mnemonic);
+                    ui.label(RichText::new(masked_seed).monospace().color(Color32::LIGHT_YELLOW));
+
+                    if ui.small_button("👁 Show").clicked() {
+                        // Set the current seed to be shown in a modal
+                        self.show_unmasked_seed = true;
+                        self.current_unmasked_seed = mnemonic.to_string();
+                    }
+                });
+            } else {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new(mnemonic).monospace().color(Color32::LIGHT_YELLOW));
+                });
+            }
+        }
+
+        if !self.qr_only_seed && self.seed_output_format.shows_hex() {
+            ui.horizontal(|ui| {
+                ui.strong("Seed entropy (hex):");
+            });
+            match seed_entropy_hex(mnemonic) {
+                Ok(hex) => {
+                    let shown = if self.mask_seed_phrases {
+                        self.mask_sensitive_data(hex.as_str())
+                    } else {
+                        hex.to_string()
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(RichText::new(shown).monospace().color(Color32::LIGHT_YELLOW));
+                    });
+                }
+                Err(e) => {
+                    ui.colored_label(Color32::from_rgb(224, 108, 117), format!("Error: {}", e));
+                }
+            }
+        }
+    }
+
+    /// Copy-seed and paper wallet buttons for a result
+    fn result_seed_actions(&mut self, ui: &mut Ui, result: &MatchResult) {
+        let (mnemonic, address, _, position, word_count, _) = result;
+        let simulated = mnemonic == SIMULATED_SEED;
+        ui.horizontal(|ui| {
+            let copy_label = if self.file_only { "💾 Save seed" } else { "📋 Copy seed" };
+            if ui.add_enabled(!self.qr_only_seed, egui::Button::new(copy_label).small()).clicked()
+                && self.copy_text(ui.ctx(), mnemonic, "Seed phrase", &format!("ergo-seed-{}.txt", &address[..10]))
+            {
+                self.clipboard_clear_at = Some(Instant::now() + CLIPBOARD_CLEAR_AFTER);
+                self.add_log(&format!(
+                    "Seed phrase copied to clipboard - it will be cleared in {}s",
+                    CLIPBOARD_CLEAR_AFTER.as_secs()
+                ));
+            }
+
+            // Add paper wallet generation button
+            if ui.add_enabled(!simulated, egui::Button::new("📄 Generate Paper Wallet").small()).clicked() {
+                let paper_wallet_info = PaperWalletInfo {
+                    address: address.clone(),
+                    mnemonic: mnemonic.clone(),
+                    word_count: *word_count,
+                    position: *position,
+                    qr_only_seed: self.qr_only_seed,
+                    per_word_qr: false,
+                    metadata: None,
+                    restore_checklist: false,
+                    qr_cards: DEFAULT_QR_CARDS,
+                    verify_qr: false,
+                };
+
+                self.pending_paper_wallet = Some(paper_wallet_info);
+            }
+        });
+    }
+
+    /// Weight fields for each seed length, shown when "All lengths" is selected
+    fn render_word_mix(&mut self, ui: &mut egui::Ui) {
+        let mut save = false;
+        ui.horizontal(|ui| {
+            ui.label("Mix:");
+            for (weight, words) in self.word_mix.iter_mut().zip([12, 15, 24]) {
+                let response = ui.add(egui::DragValue::new(weight).clamp_range(0..=100).suffix(format!(" × {}w", words)));
+                // Save once editing ends rather than on every step of a drag
+                save |= response.drag_released() || (response.changed() && !response.dragged());
+            }
+        });
+        let total: u32 = self.word_mix.iter().sum();
+        if total == 0 {
+            ui.colored_label(Color32::from_rgb(224, 108, 117), "Give at least one length a weight");
+        } else {
+            let share = |w: u32| w as f64 * 100.0 / total as f64;
+            ui.label(
+                RichText::new(format!(
+                    "12 words {:.0}% • 15 words {:.0}% • 24 words {:.0}%",
+                    share(self.word_mix[0]), share(self.word_mix[1]), share(self.word_mix[2])
+                ))
+                .color(Color32::from_rgb(200, 200, 200))
+                .size(12.0),
+            )
+            .on_hover_text("Shorter seeds are faster to generate, longer seeds are more secure");
+        }
+        if save {
+            self.save_settings();
+        }
+    }
+
+    /// Restores display preferences saved by `save_settings`
+    fn load_settings(&mut self) {
+        let Some(value) = settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        else {
+            return;
+        };
+        if let Some(short) = value["short_addresses"].as_bool() {
+            self.short_addresses = short;
+        }
+        if let Some(table) = value["results_table"].as_bool() {
+            self.results_table = table;
+        }
+        if let Some(mask) = value["seed_mask"].as_str().and_then(SeedMask::from_key) {
+            self.seed_mask = mask;
+        }
+        if let Some(mix) = value["word_mix"].as_array() {
+            let weights: Vec<u32> = mix.iter().filter_map(|w| w.as_u64()).map(|w| w.min(100) as u32).collect();
+            if let Ok(mix) = WordMix::try_from(weights) {
+                self.word_mix = mix;
+            }
+        }
+    }
+
+    /// Stores display preferences so the next session starts with them
+    fn save_settings(&mut self) {
+        let Some(path) = settings_path() else {
+            return;
+        };
+        let data = serde_json::json!({
+            "short_addresses": self.short_addresses,
+            "results_table": self.results_table,
+            "seed_mask": self.seed_mask.key(),
+            "word_mix": self.word_mix,
+        });
+        if let Err(e) = std::fs::write(&path, data.to_string()) {
+            self.add_log(&format!("Could not save settings to {}: {}", path.display(), e));
+        }
+    }
+
+    /// Patterns of the configured search as `(pattern, mode)` for the estimator
+    fn estimated_patterns(&self) -> Vec<(String, MatchMode)> {
+        let default_mode = MatchMode::from_flags(self.start_match, self.end_match);
+        self.input_patterns
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|spec| {
+                let (mode, pattern, _) = parse_pattern_spec(spec, default_mode);
+                (pattern, mode)
+            })
+            .collect()
+    }
+
+    /// Expected time for the configured search at the estimator's conservative speed
+    fn expected_search_seconds(&self) -> f64 {
+        estimator::expected_search_seconds(&self.estimated_patterns(), self.num_results, self.balanced, false, crate::utils::p2pk_address_length(), None)
+    }
+
+    /// Confirmation shown when a search is expected to exceed the difficulty budget
+    fn show_long_search_dialog(&mut self, ctx: &egui::Context) {
+        let seconds = self.confirm_long_search.unwrap_or_default();
+        let mut start = false;
+        let mut cancel = false;
+        egui::Window::new("⏳ Very Long Search")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "This search is expected to take about {} at 6,000 addresses/s,",
+                    estimator::format_time(seconds)
+                ));
+                ui.label(format!(
+                    "more than {} days. Shorter patterns are found dramatically faster.",
+                    estimator::DEFAULT_MAX_EXPECTED_DAYS
+                ));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Start anyway").clicked() {
+                        start = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if start || cancel {
+            self.confirm_long_search = None;
+        }
+        if start {
+            self.start_search();
+        }
+    }
+
+    /// Dialog shown before saving a paper wallet to choose seed encryption options
+    fn show_paper_wallet_dialog(&mut self, ctx: &egui::Context) {
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new("📄 Paper Wallet Options")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.paper_wallet_encrypt, "Encrypt seed QR code with a password")
+                    .on_hover_text("AES-256-GCM with a key derived from the password; recover the seed with --decrypt-wallet");
+                if self.paper_wallet_encrypt {
+                    egui::Grid::new("paper_wallet_encryption_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Password:");
+                            ui.add(TextEdit::singleline(&mut *self.paper_wallet_password).password(true));
+                            ui.end_row();
+                            ui.label("Confirm:");
+                            ui.add(TextEdit::singleline(&mut *self.paper_wallet_password_confirm).password(true));
+                            ui.end_row();
+                            ui.label("Hint (optional):");
+                            ui.text_edit_singleline(&mut self.paper_wallet_hint)
+                                .on_hover_text("Printed on the wallet - never put the password itself here");
+                            ui.end_row();
                         });
+                    if self.paper_wallet_password.is_empty() {
+                        ui.colored_label(Color32::from_rgb(224, 108, 117), "Enter a password");
+                    } else if *self.paper_wallet_password != *self.paper_wallet_password_confirm {
+                        ui.colored_label(Color32::from_rgb(224, 108, 117), "Passwords do not match");
+                    }
+                }
+
+                ui.add_enabled(
+                    !self.paper_wallet_encrypt,
+                    egui::Checkbox::new(&mut self.paper_wallet_word_qrs, "Add a page with one QR code per word"),
+                )
+                .on_hover_text("For backup tools that scan one word at a time. Not available with encryption, which the per-word codes would bypass");
+                ui.checkbox(&mut self.paper_wallet_metadata, "Include generation metadata")
+                    .on_hover_text("Print the creation time, tool version, derivation path and network on the wallet");
+                ui.checkbox(&mut self.paper_wallet_restore_checklist, "Include a restore checklist")
+                    .on_hover_text("Print the addresses at index 0 and at the matched index, to tick off after restoring the seed in a wallet");
+                ui.horizontal(|ui| {
+                    ui.label("Detachable QR cards:");
+                    ui.add(egui::Slider::new(&mut self.paper_wallet_qr_cards, 0..=MAX_QR_CARDS))
+                        .on_hover_text("Address QR cards to cut off the bottom of the page; 0 leaves the whole page to the folded wallet");
+                });
+                ui.checkbox(&mut self.paper_wallet_verify_qr, "Verify address QR codes")
+                    .on_hover_text("Decode the address QR codes back before saving, as is always done for seed QR codes");
+
+                ui.add_space(10.0);
+                let can_save = !self.paper_wallet_encrypt
+                    || (!self.paper_wallet_password.is_empty()
+                        && *self.paper_wallet_password == *self.paper_wallet_password_confirm);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(can_save, egui::Button::new("Save...")).clicked() {
+                        save = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if save {
+            let encryption = self.paper_wallet_encrypt.then(|| EncryptionOptions {
+                encrypt_seed: true,
+                password_hint: Some(self.paper_wallet_hint.trim().to_string()).filter(|h| !h.is_empty()),
+                password: Some(self.paper_wallet_password.clone()),
+            });
+            if let Some(mut info) = self.pending_paper_wallet.take() {
+                info.per_word_qr = self.paper_wallet_word_qrs && !self.paper_wallet_encrypt;
+                info.restore_checklist = self.paper_wallet_restore_checklist;
+                info.qr_cards = self.paper_wallet_qr_cards;
+                info.verify_qr = self.paper_wallet_verify_qr;
+                if self.paper_wallet_metadata {
+                    info.metadata = Some(ResultMetadata::new(info.word_count, info.position, ERGO_COIN_TYPE).with_label(self.result_label.clone()));
+                }
+                self.generate_paper_wallet(info, encryption);
+            }
+        }
+        if save || cancel {
+            self.pending_paper_wallet = None;
+            self.paper_wallet_password = Zeroizing::new(String::new());
+            self.paper_wallet_password_confirm = Zeroizing::new(String::new());
+        }
+    }
+
+    /// Dialog where a pasted seed phrase shows its first derived addresses, derived off the UI
+    /// thread. The input is masked and cleared (zeroized) once derivation starts.
+    fn show_seed_preview_dialog(&mut self, ctx: &egui::Context) {
+        let mut derive = false;
+        let mut close = false;
+        egui::Window::new("🔎 Preview Seed Addresses")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Paste a seed phrase to list the addresses a standard Ergo wallet derives from it.");
+                ui.label(RichText::new("Only do this on a trusted, offline machine.").color(Color32::from_rgb(224, 108, 117)));
+                ui.add_space(5.0);
+                egui::Grid::new("seed_preview_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Seed phrase:");
+                        let response = ui.add(
+                            TextEdit::singleline(&mut *self.seed_preview_input)
+                                .password(true)
+                                .desired_width(320.0),
+                        );
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            derive = true;
+                        }
+                        ui.end_row();
+                        ui.label("Addresses:");
+                        ui.add(egui::DragValue::new(&mut self.seed_preview_count).clamp_range(1..=SEED_PREVIEW_MAX));
+                        ui.end_row();
                     });
-                    ui.add_space(5.0);
+                ui.add_space(5.0);
+
+                match self.seed_preview.as_ref().map(|p| p.ready()) {
+                    Some(None) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Deriving addresses...");
+                        });
+                    }
+                    Some(Some(Ok(addresses))) => {
+                        egui::Grid::new("seed_preview_addresses")
+                            .num_columns(2)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for info in addresses {
+                                    ui.label(RichText::new(derivation_path_string(ERGO_COIN_TYPE, 0, info.position))
+                                        .color(Color32::LIGHT_GRAY));
+                                    ui.label(RichText::new(&info.address).monospace());
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Some(Some(Err(e))) => {
+                        ui.colored_label(Color32::from_rgb(224, 108, 117), e);
+                    }
+                    None => {}
                 }
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let deriving = self.seed_preview.as_ref().is_some_and(|p| p.ready().is_none());
+                    if ui.add_enabled(!deriving && !self.seed_preview_input.trim().is_empty(), egui::Button::new("Derive"))
+                        .clicked()
+                    {
+                        derive = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
             });
+
+        if derive && !self.seed_preview_input.trim().is_empty() {
+            // The old input is zeroized when dropped; the thread owns the only copy
+            let seed = std::mem::replace(&mut self.seed_preview_input, Zeroizing::new(String::new()));
+            let count = self.seed_preview_count.clamp(1, SEED_PREVIEW_MAX);
+            self.seed_preview = Some(Promise::spawn_thread("seed_preview", move || {
+                let phrase = Zeroizing::new(seed.split_whitespace().collect::<Vec<_>>().join(" "));
+                verify_mnemonic(&phrase).map_err(|e| format!("Invalid seed phrase: {}", e))?;
+                Ok(generate_addresses(&phrase, count, AddressFormat::default(), ERGO_COIN_TYPE))
+            }));
+        }
+        if close {
+            self.show_seed_preview = false;
+            self.seed_preview_input = Zeroizing::new(String::new());
+            self.seed_preview = None;
         }
     }
 
     /// Generate a paper wallet HTML and prompt user to save it
-    fn generate_paper_wallet(&mut self, info: PaperWalletInfo) {
+    fn generate_paper_wallet(&mut self, info: PaperWalletInfo, encryption: Option<EncryptionOptions>) {
         // Open a save file dialog
         match FileDialog::new()
             .set_title("Save Paper Wallet")
-            .set_directory(".")
-            .set_file_name(format!("ergo-paper-wallet-{}.html", &info.address[..10]))
+            .set_directory(self.output_dir.as_deref().unwrap_or(Path::new(".")))
+            .set_file_name(match &self.result_label {
+                Some(label) => format!("ergo-paper-wallet-{}-{}.html", label, &info.address[..10]),
+                None => format!("ergo-paper-wallet-{}.html", &info.address[..10]),
+            })
             .add_filter("HTML Files", &["html"])
             .save_file() {
                 Some(path) => {
                     // Generate the paper wallet HTML
-                    match crate::paper_wallet::generate_paper_wallet(&info, &path, None) {
+                    match crate::paper_wallet::generate_paper_wallet(&info, &path, encryption) {
                         Ok(_) => {
                             self.add_log(&format!("Paper wallet saved to {}", path.display()));
 
@@ -1058,38 +2035,50 @@ impl VanityGenApp {
         });
     }
 
-    /// Add a new helper function to mask sensitive data
+    /// Masks each word of `data` as chosen by `seed_mask`
     fn mask_sensitive_data(&self, data: &str) -> String {
-        let words: Vec<&str> = data.split_whitespace().collect();
-        let mut masked = String::new();
-
-        for (i, word) in words.iter().enumerate() {
-            if i > 0 {
-                masked.push(' ');
-            }
+        self.seed_mask.apply(data)
+    }
 
-            if word.len() <= 2 {
-                masked.push_str(word);
-            } else {
-                // Show first character and last character, mask the rest
-                let first = word.chars().next().unwrap();
-                let last = word.chars().last().unwrap();
-                let mask_len = word.len() - 2;
-                masked.push(first);
-                masked.push_str(&"•".repeat(mask_len));
-                masked.push(last);
-            }
+    /// Puts `text` (a `what`) on the clipboard and returns true. In file-only mode the
+    /// clipboard is left alone: `text` is saved to a file the user picks instead, and false
+    /// is returned.
+    fn copy_text(&mut self, ctx: &egui::Context, text: &str, what: &str, file_name: &str) -> bool {
+        if !self.file_only {
+            ctx.output_mut(|o| o.copied_text = text.to_string());
+            return true;
         }
+        let Some(path) = FileDialog::new()
+            .set_title(format!("Save {}", what))
+            .set_directory(self.output_dir.as_deref().unwrap_or(Path::new(".")))
+            .set_file_name(file_name)
+            .add_filter("Text Files", &["txt"])
+            .save_file()
+        else {
+            return false;
+        };
+        match std::fs::write(&path, format!("{}\n", text)) {
+            Ok(()) => self.add_log(&format!("{} saved to {}", what, path.display())),
+            Err(e) => self.add_log(&format!("Error: Could not save {} to {}: {}", what.to_lowercase(), path.display(), e)),
+        }
+        false
+    }
 
-        masked
+    /// Overwrites the clipboard and cancels any pending clear
+    fn clear_clipboard(&mut self, ctx: &egui::Context) {
+        ctx.output_mut(|o| o.copied_text = CLIPBOARD_PLACEHOLDER.to_string());
+        self.clipboard_clear_at = None;
     }
 
-    /// Handle window close event
     fn handle_window_close(&mut self) {
         if *self.running.lock().unwrap() {
             self.add_log("Window closing - stopping search...");
+            let search = self.promise.take();
             self.stop_search();
-            std::thread::sleep(std::time::Duration::from_millis(100));
+            // Join the search thread so it doesn't outlive the window
+            if let Some(search) = search {
+                search.block_until_ready();
+            }
         }
 
         if let Some(processor) = &self.processor {
@@ -1098,6 +2087,54 @@ impl VanityGenApp {
         self.promise = None;
     }
 
+    /// Replaces the patterns with those read from a QR code image and validates them
+    fn import_patterns_from_qr(&mut self) {
+        let Some(path) = FileDialog::new()
+            .set_title("Import Patterns from QR Code")
+            .set_directory(".")
+            .add_filter("Images", &["png", "jpg", "jpeg"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        let content = match decode_qr_image(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                self.add_log(&format!("Error: {}", e));
+                self.current_tab = Tab::Log;
+                return;
+            }
+        };
+        let patterns: Vec<&str> = content
+            .split([',', '\n', '\r'])
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        if patterns.is_empty() {
+            self.add_log(&format!("Error: QR code in {} contains no patterns", path.display()));
+            self.current_tab = Tab::Log;
+            return;
+        }
+        self.input_patterns = patterns.join(", ");
+        self.add_log(&format!("Imported {} pattern(s) from {}", patterns.len(), path.display()));
+
+        // Same checks as starting a search, reported up front
+        if let Some(invalid_chars) = self.validate_patterns() {
+            let invalid_str: String = invalid_chars.iter().collect();
+            self.add_log(&format!("Error: Invalid characters in pattern: {}", invalid_str));
+            self.current_tab = Tab::Log;
+        } else if let Err(err) = PatternMatcher::new(
+            patterns.iter().map(|p| p.to_string()).collect(),
+            self.case_sensitive,
+            self.start_match,
+            self.end_match,
+        ).validate() {
+            self.add_log(&format!("Error: {}", err));
+            self.current_tab = Tab::Log;
+        }
+    }
+
     /// Validates input patterns and returns invalid characters if any
     fn validate_patterns(&self) -> Option<Vec<char>> {
         let patterns: Vec<String> = self.input_patterns
@@ -1108,7 +2145,9 @@ impl VanityGenApp {
 
         let mut invalid_chars = Vec::new();
 
-        for pattern in patterns {
+        for spec in patterns {
+            // Strip any `start:`/`end:`/`any:` mode prefix before checking characters
+            let (_, pattern, _) = parse_pattern_spec(&spec, MatchMode::Anywhere);
             for c in pattern.chars() {
                 if !self.is_base58_char(c) && !invalid_chars.contains(&c) {
                     invalid_chars.push(c);
@@ -1125,16 +2164,12 @@ impl VanityGenApp {
 
     /// Check if character is valid Base58
     fn is_base58_char(&self, c: char) -> bool {
-        match c {
-            '0' | 'O' | 'I' | 'l' => false,
-            '1'..='9' | 'A'..='H' | 'J'..='N' | 'P'..='Z' | 'a'..='k' | 'm'..='z' => true,
-            _ => false,
-        }
+        estimator::is_base58_char(c)
     }
 }
 
 /// Runs the GUI application.
-pub fn run_gui() -> Result<(), eframe::Error> {
+pub fn run_gui(output_dir: Option<PathBuf>, file_only: bool, verify_qr: bool) -> Result<(), eframe::Error> {
     let options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1440.0, 768.0])
@@ -1146,5 +2181,103 @@ pub fn run_gui() -> Result<(), eframe::Error> {
         ..Default::default()
     };
 
-    eframe::run_native("Ergo Vanitygen", options, Box::new(|_cc| Box::new(VanityGenApp::default())))
+    let mut app = VanityGenApp::default();
+    app.load_settings();
+    app.output_dir = output_dir;
+    app.file_only = file_only;
+    app.file_only_locked = file_only;
+    app.paper_wallet_verify_qr = verify_qr;
+    eframe::run_native("Ergo Vanitygen", options, Box::new(|_cc| Box::new(app)))
+}
+
+/// Each pattern's matches and expected seconds until it reaches `target` at `address_rate`
+/// (zero once reached, `None` while the rate is unknown). Tally keys are compared ignoring
+/// case, since case-insensitive searches report patterns lowercased.
+fn pattern_progress(tally: &[(String, usize)], attempts: &[(String, f64)], target: usize, address_rate: f64) -> Vec<(String, usize, Option<f64>)> {
+    attempts
+        .iter()
+        .map(|(pattern, attempts_needed)| {
+            let count = tally
+                .iter()
+                .find(|(tallied, _)| tallied.eq_ignore_ascii_case(pattern))
+                .map_or(0, |(_, count)| *count);
+            let remaining = target.saturating_sub(count);
+            let eta = if remaining == 0 {
+                Some(0.0)
+            } else {
+                (address_rate > 0.0).then(|| remaining as f64 * attempts_needed / address_rate)
+            };
+            (pattern.clone(), count, eta)
+        })
+        .collect()
+}
+
+/// ETA from `pattern_progress` as shown in the panel and log
+fn format_eta(eta: Option<f64>) -> String {
+    match eta {
+        Some(seconds) if seconds <= 0.0 => "done".to_string(),
+        Some(seconds) => format!("ETA ~{}", estimator::format_time(seconds)),
+        None => "ETA unknown".to_string(),
+    }
+}
+
+/// Address text with the matched `(offset, len)` span emphasized. When `short`, only the
+/// first and last few characters are shown, around an ellipsis.
+fn highlighted_address(address: &str, span: Option<(usize, usize)>, short: bool) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let plain = egui::TextFormat { color: Color32::LIGHT_GREEN, ..Default::default() };
+    let highlight = egui::TextFormat {
+        color: MATCH_HIGHLIGHT,
+        underline: egui::Stroke::new(1.0, MATCH_HIGHLIGHT),
+        ..Default::default()
+    };
+    let (start, end) = span.map_or((0, 0), |(offset, len)| (offset, offset + len));
+    let parts = if short && address.len() > SHORT_ADDRESS_HEAD + SHORT_ADDRESS_TAIL {
+        vec![(0, SHORT_ADDRESS_HEAD), (address.len() - SHORT_ADDRESS_TAIL, address.len())]
+    } else {
+        vec![(0, address.len())]
+    };
+    for (i, &(from, to)) in parts.iter().enumerate() {
+        if i > 0 {
+            job.append("…", 0.0, plain.clone());
+        }
+        let (hl_from, hl_to) = (start.clamp(from, to), end.clamp(from, to));
+        job.append(&address[from..hl_from], 0.0, plain.clone());
+        job.append(&address[hl_from..hl_to], 0.0, highlight.clone());
+        job.append(&address[hl_to..to], 0.0, plain.clone());
+    }
+    job
+}
+
+fn settings_path() -> Option<std::path::PathBuf> {
+    home_file(SETTINGS_FILE)
+}
+
+/// Paints a seed's QR code grid (see `qr_modules`) as dark modules on a white quiet zone,
+/// without any text
+fn show_seed_qr(ui: &mut egui::Ui, grid: &Result<(usize, Vec<bool>), String>) {
+    const MODULE_SIZE: f32 = 3.0;
+    const QUIET_ZONE: usize = 4;
+
+    let (width, modules) = match grid {
+        Ok((width, modules)) => (*width, modules),
+        Err(e) => {
+            ui.colored_label(Color32::from_rgb(224, 108, 117), format!("Error: {}", e));
+            return;
+        }
+    };
+
+    let side = (width + 2 * QUIET_ZONE) as f32 * MODULE_SIZE;
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, Color32::WHITE);
+    for (i, dark) in modules.iter().enumerate() {
+        if !dark {
+            continue;
+        }
+        let x = (i % width + QUIET_ZONE) as f32 * MODULE_SIZE;
+        let y = (i / width + QUIET_ZONE) as f32 * MODULE_SIZE;
+        let min = rect.min + egui::vec2(x, y);
+        painter.rect_filled(egui::Rect::from_min_size(min, egui::vec2(MODULE_SIZE, MODULE_SIZE)), 0.0, Color32::BLACK);
+    }
 }
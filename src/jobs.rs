@@ -0,0 +1,218 @@
+//! `--jobs`: runs several searches from a TOML file one after another, e.g. overnight.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use serde::Deserialize;
+use crate::address_processor::AddressProcessor;
+use crate::event_socket::EventOptions;
+use crate::jsonl_output::JsonlWriter;
+use crate::matcher::PatternMatcher;
+use crate::metadata::sanitize_label;
+use crate::utils::group_digits;
+
+/// A jobs file: one `[[job]]` table per search
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobsFile {
+    job: Vec<Job>,
+}
+
+/// Where unprefixed patterns of a job must appear
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum JobMode {
+    Start,
+    End,
+    #[default]
+    Any,
+}
+
+/// One search. Only `patterns` is required; the rest default like the command line.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Job {
+    /// Names the job and its output file (default: job-N)
+    label: Option<String>,
+    patterns: Vec<String>,
+    #[serde(default)]
+    mode: JobMode,
+    #[serde(default = "default_num")]
+    num: usize,
+    /// 12, 15 or 24 (default), or 0 for a random length per seed
+    #[serde(default = "default_word_count")]
+    word_count: usize,
+    #[serde(default = "default_addresses_per_seed")]
+    addresses_per_seed: u32,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    balanced: bool,
+    /// Seconds after which the job stops with whatever it found
+    timeout: Option<u64>,
+}
+
+fn default_num() -> usize {
+    1
+}
+
+fn default_word_count() -> usize {
+    24
+}
+
+fn default_addresses_per_seed() -> u32 {
+    1
+}
+
+impl Job {
+    fn matcher(&self) -> PatternMatcher {
+        let (start, end) = match self.mode {
+            JobMode::Start => (true, false),
+            JobMode::End => (false, true),
+            JobMode::Any => (false, false),
+        };
+        PatternMatcher::new(self.patterns.clone(), self.case_sensitive, start, end)
+    }
+
+    /// Rejects settings the search itself would reject, before any job starts
+    fn validate(&self) -> Result<(), String> {
+        if ![0, 12, 15, 24].contains(&self.word_count) {
+            return Err(format!("word_count must be 12, 15, 24 or 0 (random), got {}", self.word_count));
+        }
+        if self.num == 0 || self.addresses_per_seed == 0 {
+            return Err("num and addresses_per_seed must be at least 1".to_string());
+        }
+        let matcher = self.matcher();
+        matcher.validate()?;
+        if !matcher.duplicates().is_empty() {
+            return Err(format!("duplicate patterns: {}", matcher.duplicates().join(", ")));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one job: (label, matches found, addresses checked, duration, output file or error)
+type JobSummary = (String, usize, usize, Duration, Result<PathBuf, String>);
+
+/// Reads the jobs in `path` and runs them in order, appending each job's matches to
+/// `<label>.jsonl` in `output_dir`. A job that fails stops the batch unless `continue_on_error`
+/// is set. Prints a summary per job and returns an error if any job failed.
+pub fn run(path: &Path, output_dir: &Path, continue_on_error: bool, include_seed: bool) -> Result<(), String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    let jobs: JobsFile = toml::from_str(&contents).map_err(|e| format!("Invalid jobs file {}: {}", path.display(), e))?;
+
+    let labels: Vec<String> = jobs.job
+        .iter()
+        .enumerate()
+        .map(|(i, job)| job.label.as_deref().and_then(sanitize_label).unwrap_or_else(|| format!("job-{}", i + 1)))
+        .collect();
+    if let Some(label) = labels.iter().enumerate().find_map(|(i, l)| labels[..i].contains(l).then_some(l)) {
+        return Err(format!("Two jobs are labeled '{}'; their results would share a file", label));
+    }
+    validate_all(&jobs.job, &labels)?;
+    println!("Running {} jobs from {}", jobs.job.len(), path.display());
+
+    let mut summaries: Vec<JobSummary> = Vec::new();
+    for (job, label) in jobs.job.iter().zip(labels) {
+        println!("\n=== Job '{}': {} ===", label, job.patterns.join(", "));
+        let start = Instant::now();
+        let outcome = run_job(job, &label, output_dir, include_seed);
+        let (found, checked) = outcome.as_ref().map(|&(found, checked, _)| (found, checked)).unwrap_or_default();
+        let output = outcome.map(|(_, _, file)| file);
+        if let Err(e) = &output {
+            eprintln!("Job '{}' failed: {}", label, e);
+        }
+        let failed = output.is_err();
+        summaries.push((label, found, checked, start.elapsed(), output));
+        if failed && !continue_on_error {
+            println!("Stopping the batch (pass --continue-on-error to run the remaining jobs)");
+            break;
+        }
+    }
+
+    println!("\nJob Summary");
+    println!("===========");
+    for (label, found, checked, duration, output) in &summaries {
+        match output {
+            Ok(file) => println!(
+                "- {}: {} matches, {} addresses in {:.1}s -> {}",
+                label,
+                found,
+                group_digits(*checked as u64),
+                duration.as_secs_f64(),
+                file.display()
+            ),
+            Err(e) => println!("- {}: FAILED ({})", label, e),
+        }
+    }
+    let failed = summaries.iter().filter(|(.., output)| output.is_err()).count();
+    let skipped = jobs.job.len() - summaries.len();
+    if failed > 0 || skipped > 0 {
+        return Err(format!("{} jobs failed, {} not run", failed, skipped));
+    }
+    Ok(())
+}
+
+/// Checks every job before any starts, so a mistake in a late job doesn't surface hours into
+/// the batch. The error lists each invalid job.
+fn validate_all(jobs: &[Job], labels: &[String]) -> Result<(), String> {
+    let errors: Vec<String> = jobs
+        .iter()
+        .zip(labels)
+        .filter_map(|(job, label)| job.validate().err().map(|e| format!("Job '{}': {}", label, e)))
+        .collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(format!("{} of {} jobs are invalid, none were run:\n  {}", errors.len(), jobs.len(), errors.join("\n  ")))
+}
+
+/// Runs one job with its own processor; returns (matches, addresses checked, output file)
+fn run_job(job: &Job, label: &str, output_dir: &Path, include_seed: bool) -> Result<(usize, usize, PathBuf), String> {
+    let file = output_dir.join(format!("{}.jsonl", label));
+    let options = EventOptions { include_seed, include_metadata: true, label: Some(label.to_string()), coin_type: None };
+    let writer = JsonlWriter::open(&file, options)?;
+
+    let processor = AddressProcessor::new();
+    processor.set_timeout(job.timeout.map(Duration::from_secs));
+    processor.set_result_callback(move |mnemonic, address, pattern, position, word_count, checked| {
+        writer.write_match(mnemonic, address, pattern, position, word_count, checked);
+    });
+    let results = processor.find_matches(job.matcher(), job.word_count, job.num, job.balanced, job.addresses_per_seed);
+    let (_, checked, ..) = processor.get_stats();
+    Ok((results.len(), checked, file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_invalid_job_is_reported_before_any_runs() {
+        let jobs: JobsFile = toml::from_str(
+            r#"
+            [[job]]
+            label = "good"
+            patterns = ["abc"]
+
+            [[job]]
+            label = "bad-words"
+            patterns = ["abc"]
+            word_count = 13
+
+            [[job]]
+            label = "bad-num"
+            patterns = ["xyz"]
+            num = 0
+            "#,
+        )
+        .unwrap();
+        let labels: Vec<String> = jobs.job.iter().map(|job| job.label.clone().unwrap()).collect();
+
+        let error = validate_all(&jobs.job, &labels).unwrap_err();
+        assert!(error.starts_with("2 of 3 jobs are invalid"));
+        assert!(error.contains("Job 'bad-words': word_count"));
+        assert!(error.contains("Job 'bad-num': num"));
+        assert!(!error.contains("'good'"));
+        assert!(validate_all(&jobs.job[..1], &labels[..1]).is_ok());
+    }
+}
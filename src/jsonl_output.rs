@@ -0,0 +1,43 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use crate::event_socket::{match_event, EventOptions};
+
+/// Appends match events as JSON lines to a file as they are found, so matches survive a
+/// crash and the file can be tailed. Existing lines are kept, so reruns continue the file.
+pub struct JsonlWriter {
+    file: Mutex<Option<File>>,
+    options: EventOptions,
+}
+
+impl JsonlWriter {
+    /// Opens (or creates) the file at `path` for appending.
+    pub fn open(path: &Path, options: EventOptions) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open JSONL output '{}': {}", path.display(), e))?;
+        Ok(Self {
+            file: Mutex::new(Some(file)),
+            options,
+        })
+    }
+
+    /// Appends one match as a single JSON line and syncs it to disk before returning.
+    /// After a write error the file is dropped and further matches are only printed.
+    pub fn write_match(&self, mnemonic: &str, address: &str, pattern: &str, position: u32, word_count: usize, addresses_checked: usize) {
+        let mut guard = self.file.lock().unwrap();
+        let Some(file) = guard.as_mut() else {
+            return;
+        };
+
+        let event = match_event(&self.options, mnemonic, address, pattern, position, word_count, addresses_checked);
+        let line = format!("{}\n", event);
+        if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.sync_data()) {
+            log::warn!("Could not write to the JSONL output ({}), no further matches will be written", e);
+            *guard = None;
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! The search engine behind the `ergo-vanitygen` binary: seed generation and address
+//! derivation, pattern matching, difficulty estimates and paper wallets. With the `async`
+//! feature, `async_search::search_stream` runs a search from a `tokio` application.
+
+pub mod address_processor;
+pub mod affinity;
+pub mod crypto;
+pub mod estimator;
+pub mod matcher;
+pub mod metadata;
+pub mod paper_wallet;
+pub mod progress;
+pub mod utils;
+
+#[cfg(feature = "async")]
+pub mod async_search;
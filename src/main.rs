@@ -1,37 +1,160 @@
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use clap::Parser;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-// Define modules
+// Define modules; the search engine itself is the library (see lib.rs)
 mod args;
-mod address_processor;
-mod progress;
-mod utils;
-mod matcher;
-mod estimator;
-mod paper_wallet;
-mod crypto;
+mod benchmark;
+mod event_socket;
+mod jsonl_output;
+mod jobs;
+mod build_info;
+mod autotune;
+mod rate_cache;
+mod self_test;
+mod verify;
+mod manifest;
 
 #[cfg(feature = "gui")]
 mod gui;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "profile")]
+mod profiling;
 
+use ergo_vanitygen::{address_processor, affinity, crypto, estimator, matcher, metadata, paper_wallet, progress, utils};
 use args::Args;
+use utils::{group_digits, UNIFORM_WORD_MIX};
+use zeroize::Zeroizing;
+
+/// Exit status when a run ends without finding what it was asked for: a search stopped short
+/// of its requested matches (e.g. by `--timeout`), or `--verify` not finding the address
+const EXIT_NOT_FOUND: i32 = 2;
+
+/// Address characters per row of the match offset histogram, and the longest bar
+const OFFSET_BUCKET: usize = 10;
+const HISTOGRAM_WIDTH: usize = 30;
 
 fn main() {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    init_logging(args.verbose);
+
+    if args.build_info {
+        build_info::print_build_info(args.json);
+        return;
+    }
+
+    if args.coin_type != utils::ERGO_COIN_TYPE {
+        eprintln!("WARNING: --coin-type {} derives addresses under m/44'/{}'/0'/0/index instead of Ergo's m/44'/429'/0'/0/index.", args.coin_type, args.coin_type);
+        eprintln!("WARNING: Standard Ergo wallets will NOT derive these addresses from the seed phrase. Funds sent to");
+        eprintln!("WARNING: them can only be recovered with a wallet that lets you enter this exact derivation path.");
+    }
+
+    if let Some(dir) = &args.output_dir {
+        if let Err(e) = utils::prepare_output_dir(dir) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    #[cfg(feature = "profile")]
+    let profile_guard = args.profile_path().and_then(|path| profiling::start(&path));
+
+    // Checked up front so benchmarks and autotuning run pinned too
+    let cpu_affinity = args.cpu_affinity.clone().filter(|cores| match affinity::check_cores(cores) {
+        Ok(true) => true,
+        Ok(false) => {
+            eprintln!("Warning: CPU affinity isn't available on this platform; --cpu-affinity is ignored");
+            false
+        }
+        Err(e) => {
+            eprintln!("Error: --cpu-affinity: {}", e);
+            std::process::exit(1);
+        }
+    });
+    if let Some(cores) = &cpu_affinity {
+        if let Err(e) = affinity::pin_global_pool(cores) {
+            log::warn!("Could not pin the global thread pool: {}", e);
+        }
+    }
+
+    if args.reset_rate_cache {
+        match rate_cache::reset() {
+            Ok(()) => println!("Cleared the measured rate cache"),
+            Err(e) => {
+                eprintln!("Error: Could not clear the rate cache: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if args.from_entropy {
+        if let Err(e) = print_from_entropy(args.addresses_per_seed.max(1), args.coin_type) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if args.self_test {
+        if let Err(e) = self_test::run() {
+            eprintln!("Self-test FAILED: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(path) = &args.jobs {
+        let output_dir = args.output_dir.clone().unwrap_or_else(|| std::path::PathBuf::from("."));
+        if let Err(e) = jobs::run(path, &output_dir, args.continue_on_error, !args.no_seed && !args.qr_only_seed) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(address) = &args.verify {
+        match verify::run(address, args.verify_indices.max(1), args.verify_accounts.max(1), args.coin_type) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(EXIT_NOT_FOUND),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if args.decrypt_wallet {
+        if let Err(e) = paper_wallet::decrypt_wallet_interactive() {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
     // Initialize hardware acceleration if available
     if cfg!(feature = "hw_accel") {
         crypto::get_context().log_features();
     }
 
+    if let Some(path) = args.replay_manifest.clone() {
+        replay_manifest(&path, &mut args);
+        return;
+    }
+    if let Some(path) = args.resume_manifest.clone() {
+        resume_manifest(&path, &mut args);
+        return;
+    }
+
     // GUI Mode check - if no explicit patterns provided and no-gui isn't specified,
     // we default to GUI mode if the feature is enabled
     #[cfg(feature = "gui")]
     {
-        let should_launch_gui = args.patterns.is_empty() && !args.no_gui && !args.estimate;
+        let should_launch_gui = args.patterns.is_empty() && !args.dictionary && args.class_run.is_none() && !args.no_gui && !args.estimate && !args.benchmark && !args.benchmark_matcher && !args.autotune;
         if should_launch_gui {
-            if let Err(e) = gui::run_gui() {
+            if let Err(e) = gui::run_gui(args.output_dir.clone(), args.file_only, args.verify_qr) {
                 eprintln!("Error running GUI: {}", e);
                 std::process::exit(1);
             }
@@ -39,6 +162,40 @@ fn main() {
         }
     }
 
+    // If benchmark flag is set, compare seed lengths and exit
+    if args.benchmark {
+        let results = benchmark::run_and_print(args.benchmark_seeds.max(1), args.addresses_per_seed);
+        if let Some(&(_, _, addr_rate)) = results.iter().find(|(wc, _, _)| *wc == args.word_count()) {
+            rate_cache::save_rate(addr_rate, args.word_count(), args.addresses_per_seed);
+        }
+        return;
+    }
+
+    // If matcher benchmark flag is set, compare pattern counts and exit
+    if args.benchmark_matcher {
+        benchmark::run_matcher_and_print();
+        return;
+    }
+
+    if let Err(err) = args.validate_batch_sizes() {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+
+    // If autotune flag is set, find the fastest batch size; without patterns, stop there
+    let tuned_batch_size = if args.autotune {
+        let (min, max) = args.batch_size_bounds();
+        let best = autotune::run_and_print(min, max, args.word_count(), args.addresses_per_seed);
+        autotune::save_recommendation(best, args.word_count(), args.addresses_per_seed);
+        if args.patterns.is_empty() {
+            return;
+        }
+        println!();
+        Some(best)
+    } else {
+        None
+    };
+
     // If estimate flag is set, run the estimation and exit
     if args.estimate {
         if args.patterns.is_empty() {
@@ -46,9 +203,28 @@ fn main() {
             std::process::exit(1);
         }
 
-        let patterns = args.patterns.clone();
-        for pattern in patterns {
-            estimator::estimate_and_print(&pattern, args.start);
+        if args.rate.is_some_and(|r| r.is_nan() || r <= 0.0) {
+            eprintln!("Error: --rate must be greater than 0");
+            std::process::exit(1);
+        }
+        if args.confidence.is_some_and(|p| !(p > 0.0 && p < 1.0)) {
+            eprintln!("Error: --confidence must be between 0 and 1 (exclusive), e.g. 0.9");
+            std::process::exit(1);
+        }
+        let rate = args.rate.or_else(|| {
+            let cached = rate_cache::load_rate(args.word_count(), args.addresses_per_seed)?;
+            // Keep stdout parseable with --json
+            let note = format!("Using the rate measured on this machine by a previous run ({} addr/s)", group_digits(cached.round() as u64));
+            if args.json { eprintln!("{}", note) } else { println!("{}", note) }
+            Some(cached)
+        });
+        if args.json {
+            let json = estimator::estimates_json(&args.estimated_patterns(), args.num, args.balanced, args.shape, rate, args.confidence, args.exact_count());
+            println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+            return;
+        }
+        for (mode, pattern, _) in args.pattern_specs() {
+            estimator::estimate_and_print(&pattern, mode, args.shape, rate, args.confidence, args.exact_count());
         }
         return;
     }
@@ -58,62 +234,560 @@ fn main() {
         eprintln!("Error: {}", err);
         std::process::exit(1);
     }
+    let matcher = args.create_matcher();
+    for warning in args.warnings().into_iter().chain(matcher.warnings()) {
+        eprintln!("Warning: {}", warning);
+    }
+    if let Some(path) = &args.seeds_file {
+        check_seeds_file(path, &matcher, args.addresses_per_seed, args.coin_type, args.one_per_seed);
+        return;
+    }
+    if let Some(hex) = &args.seed_sequence {
+        let manifest_path = args.manifest_path();
+        if let Some(path) = manifest_path.as_ref().filter(|path| path.exists()) {
+            eprintln!("Error: {} already exists; continue it with --resume-manifest", path.display());
+            std::process::exit(1);
+        }
+        let matches = check_seed_sequence(hex, args.seed_sequence_start, args.seed_sequence_count, &matcher, args.addresses_per_seed, args.coin_type, args.one_per_seed);
+        if let Some(path) = &manifest_path {
+            let manifest = manifest::Manifest::new(&args, hex, args.seed_sequence_start, args.seed_sequence_count, &matches);
+            save_manifest(&manifest, path);
+        }
+        return;
+    }
+    if let Err(err) = args.check_difficulty_budget() {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
 
     // Print processing information
-    println!(
-        "Looking for {} addresses matching {} patterns {}{}",
-        args.num,
-        args.patterns.len(),
-        if args.start { "starting with " } else if args.end { "ending with " } else { "containing " },
-        args.patterns.join(", ")
-    );
-    println!("Using {}-word seed phrases", args.word_count());
+    let mixed_modes = args.pattern_specs().iter().any(|(_, _, explicit)| *explicit);
+    if args.dictionary {
+        println!(
+            "Looking for {} addresses containing any of {} dictionary words ({}+ letters)",
+            args.num,
+            group_digits(matcher.dictionary_size() as u64),
+            args.dictionary_min_len
+        );
+    }
+    if let Some((len, class)) = args.class_run {
+        let estimate = estimator::estimate_class_run(len, class, utils::AddressFormat::default());
+        println!(
+            "Looking for {} addresses whose characters {}-{} are all {} (about {} attempts each)",
+            args.num,
+            matcher::CLASS_RUN_OFFSET + 1,
+            matcher::CLASS_RUN_OFFSET + len,
+            class.description(),
+            group_digits(estimate.attempts_needed.round() as u64)
+        );
+    }
+    if !args.patterns.is_empty() {
+        println!(
+            "Looking for {} addresses matching {} patterns {}{}",
+            args.num,
+            matcher.pattern_count(),
+            if mixed_modes { "" } else if args.start { "starting with " } else if args.end { "ending with " } else { "containing " },
+            args.patterns.join(", ")
+        );
+    }
+    if let Some(label) = &args.label {
+        println!("Search label: {}", label);
+    }
+    if args.simulate {
+        println!("SIMULATION MODE: addresses are random strings, results are NOT real wallets");
+    }
+    match (args.word_count(), args.word_mix) {
+        (0, Some([w12, w15, w24])) => println!("Using 12, 15 and 24-word seed phrases (weights {}:{}:{})", w12, w15, w24),
+        (0, None) => println!("Using 12, 15 and 24-word seed phrases"),
+        (word_count, _) => println!("Using {}-word seed phrases", word_count),
+    }
     println!("Checking {} addresses per seed", args.addresses_per_seed);
+    if args.coin_type != utils::ERGO_COIN_TYPE {
+        println!("Deriving addresses under m/44'/{}'/0'/0/index (non-standard coin type)", args.coin_type);
+    }
+    if args.entropy == utils::EntropySource::Os {
+        println!("Drawing seed entropy from the operating system RNG");
+    }
+    if args.prefer_low_index && args.addresses_per_seed > 1 {
+        println!("Preferring matches at low address indices");
+    }
+    if args.one_per_seed && args.addresses_per_seed > 1 {
+        println!("Keeping at most one match per seed");
+    }
 
     // Set up processor
-    let processor = address_processor::AddressProcessor::new();
+    let processor = Arc::new(match cpu_affinity {
+        Some(cores) => address_processor::AddressProcessor::pinned(cores, !args.no_progress),
+        None if args.no_progress => address_processor::AddressProcessor::without_progress_bar(),
+        None => address_processor::AddressProcessor::new(),
+    });
+    processor.set_seed_output_format(args.seed_output_format);
+    processor.set_qr_only_seed(args.qr_only_seed);
+    processor.set_compact(args.compact);
+    processor.set_mask_seed(args.mask_seed);
+    processor.set_simulate(args.simulate);
+    processor.set_timeout(args.timeout.map(Duration::from_secs));
+    processor.set_word_count_fallback(args.word_count_fallback.map(Duration::from_secs));
+    processor.set_keep_going(args.keep_going);
+    processor.set_prefer_low_index(args.prefer_low_index);
+    processor.set_coin_type(args.coin_type);
+    processor.set_one_per_seed(args.one_per_seed);
+    processor.set_all_per_seed(args.all_per_seed);
+    processor.set_entropy_source(args.entropy);
+    processor.set_word_mix(args.word_mix.unwrap_or(UNIFORM_WORD_MIX));
+    processor.set_max_results(args.max_results);
+    processor.set_track_nearest(args.nearest);
+    processor.set_expected_addresses(args.expected_addresses());
+    if log::log_enabled!(log::Level::Debug) {
+        // Periodic progress in the log, for runs where the progress bar isn't visible
+        let tag = args.label.as_ref().map(|label| format!(" [{}]", label)).unwrap_or_default();
+        processor.set_progress_callback(move |seeds, addresses, seed_rate, addr_rate| {
+            log::debug!("Progress{}: {} seeds, {} addresses ({:.0} seeds/s, {:.0} addresses/s)", tag, seeds, addresses, seed_rate, addr_rate);
+        });
+    }
+    if args.min_batch_size.is_some() || args.max_batch_size.is_some() {
+        let (min, max) = args.batch_size_bounds();
+        processor.set_batch_size_bounds(min, max);
+    }
+    if let Some(size) = args.batch_size {
+        println!("Using fixed batch size {} (adaptive sizing disabled)", size);
+        processor.pin_batch_size(size);
+    } else if let Some(size) = tuned_batch_size {
+        println!("Using tuned batch size {} (adaptive sizing disabled)", size);
+        processor.pin_batch_size(size);
+    } else if let Some(size) = autotune::load_recommendation(args.word_count(), args.addresses_per_seed) {
+        println!("Starting from batch size {} found by a previous --autotune", size);
+        processor.set_initial_batch_size(size);
+    }
     let start_time = Instant::now();
 
     // Register Ctrl+C handler
     static CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
+    let keep_going = args.keep_going;
+    let processor_for_handler = Arc::clone(&processor);
     ctrlc::set_handler(move || {
         if CANCEL_FLAG.load(Ordering::SeqCst) {
             // Second Ctrl+C, force exit
             std::process::exit(1);
         }
         CANCEL_FLAG.store(true, Ordering::SeqCst);
+        if keep_going {
+            // With --keep-going, Ctrl+C is the normal way to end the search
+            eprintln!("\nCtrl+C received, finishing search... Press Ctrl+C again to force exit.");
+            processor_for_handler.cancel();
+            return;
+        }
         eprintln!("\nCtrl+C received, attempting to cancel... Press Ctrl+C again to force exit.");
     }).expect("Error setting Ctrl+C handler");
 
+    // Stream matches to a local consumer and/or append them to a file if requested
+    let event_options = event_socket::EventOptions {
+        include_seed: false,
+        include_metadata: args.include_metadata,
+        label: args.label.clone(),
+        coin_type: Some(args.coin_type).filter(|&coin_type| coin_type != utils::ERGO_COIN_TYPE),
+    };
+    let socket = args.event_socket.as_ref().map(|path| {
+        let options = event_socket::EventOptions { include_seed: args.event_include_seed, ..event_options.clone() };
+        event_socket::EventSocket::connect(path, options).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    });
+    let jsonl = args.jsonl_path().map(|path| {
+        let options = event_socket::EventOptions { include_seed: !args.no_seed && !args.qr_only_seed, ..event_options.clone() };
+        jsonl_output::JsonlWriter::open(&path, options).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    });
+    if socket.is_some() || jsonl.is_some() {
+        processor.set_result_callback(move |mnemonic, address, pattern, position, word_count, checked| {
+            if let Some(socket) = &socket {
+                socket.send_match(mnemonic, address, pattern, position, word_count, checked);
+            }
+            if let Some(jsonl) = &jsonl {
+                jsonl.write_match(mnemonic, address, pattern, position, word_count, checked);
+            }
+        });
+    }
+
     // Run the search
-    let matcher = args.create_matcher();
-    let _results = processor.find_matches(
-        matcher,
-        args.word_count() as usize,
-        args.num,
-        args.balanced,
-        args.addresses_per_seed
-    );
+    let results = run_search(&args, &processor, matcher);
 
-    // If cancelled, print message and exit
-    if CANCEL_FLAG.load(Ordering::SeqCst) {
+    // If cancelled, print message and exit; with --keep-going this is how a search ends
+    if args.max_results.is_some_and(|max| processor.matches_found() >= max) {
+        println!("\nReached the --max-results cap of {} matches.", group_digits(processor.matches_found() as u64));
+    } else if args.keep_going {
+        println!("\nSearch stopped after {} matches.", group_digits(processor.matches_found() as u64));
+    } else if CANCEL_FLAG.load(Ordering::SeqCst) {
         println!("Search cancelled by user.");
         std::process::exit(1);
     }
 
+    // A bounded search (timeout, or Ctrl+C with --keep-going) may stop short of the requested
+    // matches; a lower --max-results cap lowers the request
+    let requested = args.num.min(args.max_results.unwrap_or(usize::MAX));
+    let found = processor.matches_found();
+    if found < requested {
+        println!(
+            "\nFound {}/{} matches{}",
+            group_digits(found as u64),
+            group_digits(requested as u64),
+            if args.timeout.is_some() && !args.keep_going { " before the timeout" } else { "" }
+        );
+    }
+
+    // Report the closest near miss if the search ran out of time without an exact match
+    if results.is_empty() && args.nearest {
+        match processor.take_nearest_match() {
+            Some(((mnemonic, address, pattern, position, wc, _), distance)) => {
+                println!("\nNo exact match found before the timeout. Closest candidate:");
+                println!("Pattern: {} (edit distance {})", pattern, distance);
+                println!("Address: {}", address);
+                println!("Position: {}", position);
+                if args.mask_seed {
+                    println!("Seed phrase ({}-word): <masked>", wc);
+                } else if args.qr_only_seed {
+                    println!("Seed phrase ({}-word), QR only - the code contains the secret:", wc);
+                    match paper_wallet::render_qr_terminal(&mnemonic) {
+                        Ok(qr) => println!("{}", qr),
+                        Err(e) => log::warn!("Could not render seed QR code: {}", e),
+                    }
+                } else {
+                    println!("Seed phrase ({}-word): {}", wc, mnemonic);
+                }
+            }
+            None => println!("\nNo exact match found before the timeout."),
+        }
+    }
+
+    // The tool chose the words, so show which word each address contains and where
+    if args.dictionary && !results.is_empty() {
+        let matcher = args.create_matcher();
+        println!("\nWords found:");
+        for (_, address, pattern, _, _, _) in &results {
+            if let Some((offset, len)) = matcher.match_span(address, pattern) {
+                println!("- \"{}\" at characters {}-{} of {}", pattern, offset + 1, offset + len, address);
+            }
+        }
+    }
+
+    print_matches_by_pattern(&results);
+    print_match_histogram(&results, &args.create_matcher());
+
     // Get and display performance stats
     let (total_seeds, total_addresses, seed_rate, address_rate, threads) = processor.get_stats();
     println!("\nPerformance Statistics:");
+    if let Some(label) = &args.label {
+        println!("- Search label: {}", label);
+    }
     println!("- Using {} threads", threads);
-    println!("- Checked {} seeds", total_seeds);
-    println!("- Checked {} addresses", total_addresses);
-    println!("- Average speed: {:.0} seeds/second", seed_rate);
-    println!("- Average speed: {:.0} addresses/second", address_rate);
+    if let Some((cores, pinned)) = processor.affinity() {
+        println!(
+            "- CPU affinity: {} ({}/{} worker threads pinned)",
+            affinity::format_core_list(cores),
+            pinned,
+            threads
+        );
+    }
+    println!("- Checked {} seeds", group_digits(total_seeds as u64));
+    println!("- Checked {} addresses", group_digits(total_addresses as u64));
+    println!("- Average speed: {} seeds/second", group_digits(seed_rate.round() as u64));
+    println!("- Average speed: {} addresses/second", group_digits(address_rate.round() as u64));
 
     // Display timing
     let duration = start_time.elapsed();
     println!("- Total search time: {:.2} seconds", duration.as_secs_f64());
 
-    // Done
-    std::process::exit(0);
-}
\ No newline at end of file
+    // Remember this machine's speed for later estimates; simulated rates aren't representative
+    if !args.simulate && duration.as_secs_f64() >= rate_cache::MIN_MEASURE_SECONDS {
+        rate_cache::save_rate(address_rate, args.word_count(), args.addresses_per_seed);
+    }
+
+    // exit() skips destructors, so flush the profile first
+    #[cfg(feature = "profile")]
+    drop(profile_guard);
+
+    // Done; scripts can tell a search that came up short from a complete one
+    std::process::exit(if found < requested { EXIT_NOT_FOUND } else { 0 });
+}
+
+/// Runs the search, in the terminal UI when `--tui` is given and stdout is a terminal
+fn run_search(args: &Args, processor: &Arc<address_processor::AddressProcessor>, matcher: matcher::PatternMatcher) -> Vec<address_processor::MatchResult> {
+    #[cfg(feature = "tui")]
+    if args.use_tui() {
+        let word_count = args.word_count();
+        return match tui::run(Arc::clone(processor), matcher, word_count, args.num, args.balanced, args.addresses_per_seed) {
+            Ok(results) => {
+                // The terminal UI only shows addresses; print the full results now that it's closed
+                processor.print_results(&results);
+                results
+            }
+            Err(e) => {
+                eprintln!("Error: terminal UI failed: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+    processor.find_matches(
+        matcher,
+        args.word_count(),
+        args.num,
+        args.balanced,
+        args.addresses_per_seed
+    )
+}
+
+/// Lists the matched addresses of a multi-pattern search per pattern, patterns in alphabetical
+/// order and each one's addresses in the order found. Seeds stay in the match log above.
+fn print_matches_by_pattern(results: &[address_processor::MatchResult]) {
+    let mut by_pattern = std::collections::BTreeMap::<&str, Vec<(&str, u32)>>::new();
+    for (_, address, pattern, position, ..) in results {
+        by_pattern.entry(pattern.as_str()).or_default().push((address.as_str(), *position));
+    }
+    if by_pattern.len() < 2 {
+        return;
+    }
+    println!("\nMatches by pattern:");
+    for (pattern, matches) in by_pattern {
+        println!("{} ({}):", pattern, matches.len());
+        for (address, position) in matches {
+            println!("- {} (position {})", address, position);
+        }
+    }
+}
+
+/// Prints how the matches spread over the patterns and over where in the address they start,
+/// so a glance shows whether the criteria are looser than intended. Skipped for fewer than two
+/// matches, and each part when every match falls in the same row.
+fn print_match_histogram(results: &[address_processor::MatchResult], matcher: &matcher::PatternMatcher) {
+    if results.len() < 2 {
+        return;
+    }
+
+    let mut per_pattern: Vec<(String, usize)> = Vec::new();
+    for (_, _, pattern, ..) in results {
+        match per_pattern.iter_mut().find(|(p, _)| p == pattern) {
+            Some((_, count)) => *count += 1,
+            None => per_pattern.push((pattern.clone(), 1)),
+        }
+    }
+    if per_pattern.len() > 1 {
+        per_pattern.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        println!("\nMatches per pattern:");
+        print_histogram(&per_pattern);
+    }
+
+    let mut per_offset = std::collections::BTreeMap::<usize, usize>::new();
+    for (_, address, pattern, ..) in results {
+        if let Some((offset, _)) = matcher.match_span(address, pattern) {
+            *per_offset.entry(offset / OFFSET_BUCKET).or_insert(0) += 1;
+        }
+    }
+    if per_offset.len() > 1 {
+        let rows: Vec<(String, usize)> = per_offset
+            .into_iter()
+            .map(|(bucket, count)| {
+                let first = bucket * OFFSET_BUCKET + 1;
+                (format!("characters {}-{}", first, first + OFFSET_BUCKET - 1), count)
+            })
+            .collect();
+        println!("\nWhere in the address the matches start:");
+        print_histogram(&rows);
+    }
+}
+
+/// Prints `(label, count)` rows with a bar scaled to the largest count
+fn print_histogram(rows: &[(String, usize)]) {
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let max = rows.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+    for (label, count) in rows {
+        let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(max));
+        println!("- {:<width$} {:>5} {}", label, count, bar, width = width);
+    }
+}
+
+/// Reads hex entropy from stdin and prints its seed phrase and first `count` addresses.
+fn print_from_entropy(count: u32, coin_type: u32) -> Result<(), String> {
+    println!("Paste 32, 40 or 64 hex characters of entropy (128, 160 or 256 bits) and press Enter:");
+    let mut input = Zeroizing::new(String::new());
+    std::io::stdin().read_line(&mut input).map_err(|e| e.to_string())?;
+    let entropy = utils::parse_hex_entropy(&input)?;
+
+    let seed = utils::generate_mnemonic_from_entropy(&entropy)?;
+    eprintln!("WARNING: anyone who knows this entropy controls the wallet. Only use entropy from a");
+    eprintln!("source you trust, never reuse it, and clear your terminal scrollback afterwards.");
+    println!("Seed phrase ({}-word): {}", seed.as_str().split_whitespace().count(), seed.as_str());
+    for info in utils::generate_addresses(seed.as_str(), count, utils::AddressFormat::default(), coin_type) {
+        println!("Address {}: {}", info.position, info.address);
+    }
+    Ok(())
+}
+
+/// Checks every mnemonic in `path` against the patterns and prints the matching addresses.
+fn check_seeds_file(path: &str, matcher: &matcher::PatternMatcher, addresses_per_seed: u32, coin_type: u32, one_per_seed: bool) {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => Zeroizing::new(contents),
+        Err(e) => {
+            eprintln!("Error: Could not read {}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    let seeds: Vec<utils::SecureSeed> = contents.lines().map(utils::SecureSeed::new).collect();
+    check_seed_list(&seeds, path, "Line", 1, matcher, addresses_per_seed, coin_type, one_per_seed);
+}
+
+/// Searches `count` seeds of the deterministic sequence of `--seed-sequence` from seed `first`,
+/// prints the matching addresses and returns them numbered by seed.
+fn check_seed_sequence(
+    hex: &str,
+    first: u64,
+    count: usize,
+    matcher: &matcher::PatternMatcher,
+    addresses_per_seed: u32,
+    coin_type: u32,
+    one_per_seed: bool,
+) -> Vec<address_processor::SeedListMatch> {
+    eprintln!("WARNING: deterministic seeds are for testing only. Anyone who knows the base entropy");
+    eprintln!("WARNING: can recreate every seed in the sequence. NEVER send real funds to these addresses.");
+    let seeds = match utils::parse_hex_entropy(hex).and_then(|entropy| utils::deterministic_mnemonics(&entropy, first, count)) {
+        Ok(seeds) => seeds,
+        Err(e) => {
+            eprintln!("Error: --seed-sequence: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let source = format!("the deterministic sequence (seeds {} to {})", first, first + count as u64 - 1);
+    check_seed_list(&seeds, &source, "Seed", first as usize, matcher, addresses_per_seed, coin_type, one_per_seed)
+}
+
+/// Checks `seeds` against the patterns and prints each match as "`item` N: ...", N counting
+/// from `first_item`. Returns the matches numbered the same way.
+#[allow(clippy::too_many_arguments)]
+fn check_seed_list(
+    seeds: &[utils::SecureSeed],
+    source: &str,
+    item: &str,
+    first_item: usize,
+    matcher: &matcher::PatternMatcher,
+    addresses_per_seed: u32,
+    coin_type: u32,
+    one_per_seed: bool,
+) -> Vec<address_processor::SeedListMatch> {
+    let checked = seeds.iter().filter(|s| !s.as_str().trim().is_empty()).count();
+    println!("Checking {} seeds from {} ({} addresses per seed)", group_digits(checked as u64), source, addresses_per_seed);
+
+    let processor = address_processor::AddressProcessor::new();
+    processor.set_coin_type(coin_type);
+    processor.set_one_per_seed(one_per_seed);
+    let (mut matches, invalid) = processor.check_seed_list(matcher, seeds, addresses_per_seed);
+    for (line, address, patterns, position) in &mut matches {
+        *line += first_item - 1;
+        println!(
+            "{} {}: {} (pattern {}, index {}, path {})",
+            item, line, address, patterns, position, utils::derivation_path_string(coin_type, 0, *position)
+        );
+    }
+    println!(
+        "\nChecked {} seeds: {} matches, {} invalid lines skipped",
+        group_digits((checked - invalid) as u64),
+        matches.len(),
+        invalid
+    );
+    matches
+}
+
+/// Writes `manifest` to `path` and says how to continue it, exiting on failure.
+fn save_manifest(manifest: &manifest::Manifest, path: &std::path::Path) {
+    if let Err(e) = manifest.save(path) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    println!(
+        "Manifest {} covers seeds {} to {} with {} matches; continue from seed {} with --resume-manifest",
+        path.display(),
+        manifest.first_seed,
+        manifest.next_seed() - 1,
+        manifest.match_count(),
+        manifest.next_seed()
+    );
+}
+
+/// Reads a manifest, taking over its criteria, and reports any patterns given on the
+/// command line as ignored. Exits on failure.
+fn load_manifest(path: &std::path::Path, args: &mut Args) -> manifest::Manifest {
+    if !args.patterns.is_empty() {
+        eprintln!("Warning: the patterns and matching options recorded in {} are used, not the ones given", path.display());
+    }
+    match manifest::Manifest::load(path, args) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--resume-manifest`: checks the next `--seed-sequence-count` seeds of a manifest's sequence
+/// and adds them to it.
+fn resume_manifest(path: &std::path::Path, args: &mut Args) {
+    let mut manifest = load_manifest(path, args);
+    let matcher = args.create_matcher();
+    let matches = check_seed_sequence(
+        &manifest.base_entropy,
+        manifest.next_seed(),
+        args.seed_sequence_count,
+        &matcher,
+        args.addresses_per_seed,
+        args.coin_type,
+        args.one_per_seed,
+    );
+    manifest.extend(args.seed_sequence_count, &matches);
+    save_manifest(&manifest, path);
+}
+
+/// `--replay-manifest`: checks every seed a manifest covers again and confirms that exactly the
+/// recorded matches turn up. Exits with `EXIT_NOT_FOUND` when they differ.
+fn replay_manifest(path: &std::path::Path, args: &mut Args) {
+    let manifest = load_manifest(path, args);
+    let Ok(count) = usize::try_from(manifest.seeds_checked) else {
+        eprintln!("Error: {} covers more seeds than can be replayed", path.display());
+        std::process::exit(1);
+    };
+    let matcher = args.create_matcher();
+    let matches = check_seed_sequence(
+        &manifest.base_entropy,
+        manifest.first_seed,
+        count,
+        &matcher,
+        args.addresses_per_seed,
+        args.coin_type,
+        args.one_per_seed,
+    );
+    let differences = manifest.differences(&matches);
+    if differences.is_empty() {
+        println!("Manifest confirmed: replaying its {} seeds found exactly its {} matches", group_digits(manifest.seeds_checked), manifest.match_count());
+        return;
+    }
+    println!("Manifest NOT confirmed:");
+    for difference in &differences {
+        println!("- {}", difference);
+    }
+    std::process::exit(EXIT_NOT_FOUND);
+}
+
+/// Sets up leveled diagnostics on stderr: warnings by default, more with each `-v`.
+/// `RUST_LOG` overrides the level when set. Match results are still printed to stdout.
+fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .init();
+}
@@ -0,0 +1,179 @@
+//! Search manifests for `--seed-sequence`: a JSON record of the base entropy, the match
+//! criteria, the range of seeds checked and the matches found. A manifest can be shared, so
+//! others can continue the sequence where it stopped (`--resume-manifest`) or check every seed
+//! again to confirm a claimed find (`--replay-manifest`), e.g. for community vanity bounties.
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::address_processor::SeedListMatch;
+use crate::args::Args;
+use crate::matcher::parse_class_run;
+
+/// Format version written to new manifests
+const MANIFEST_VERSION: u32 = 1;
+
+/// The options that decide which addresses of a seed match, as given on the command line
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Criteria {
+    patterns: Vec<String>,
+    start: bool,
+    end: bool,
+    match_case: bool,
+    shape: bool,
+    end_before_checksum: bool,
+    clean_chars: Option<String>,
+    dictionary_min_len: Option<usize>,
+    class_run: Option<String>,
+    exact_count: Option<u16>,
+    addresses_per_seed: u32,
+    coin_type: u32,
+    one_per_seed: bool,
+}
+
+impl Criteria {
+    fn from_args(args: &Args) -> Self {
+        Self {
+            patterns: args.patterns.clone(),
+            start: args.start,
+            end: args.end,
+            match_case: args.case_sensitive,
+            shape: args.shape,
+            end_before_checksum: args.end_before_checksum,
+            clean_chars: args.clean.then(|| args.clean_chars.clone()),
+            dictionary_min_len: args.dictionary.then_some(args.dictionary_min_len),
+            class_run: args.class_run.map(|(len, class)| format!("{}:{}", len, class.name())),
+            exact_count: args.exact_count,
+            addresses_per_seed: args.addresses_per_seed,
+            coin_type: args.coin_type,
+            one_per_seed: args.one_per_seed,
+        }
+    }
+
+    /// Replaces the matching options of `args` with these, so a resumed or replayed search
+    /// matches exactly like the one that wrote the manifest
+    fn apply(&self, args: &mut Args) -> Result<(), String> {
+        args.patterns = self.patterns.clone();
+        args.start = self.start;
+        args.end = self.end;
+        args.case_sensitive = self.match_case;
+        args.shape = self.shape;
+        args.end_before_checksum = self.end_before_checksum;
+        args.clean = self.clean_chars.is_some();
+        if let Some(chars) = &self.clean_chars {
+            args.clean_chars = chars.clone();
+        }
+        args.dictionary = self.dictionary_min_len.is_some();
+        if let Some(min_len) = self.dictionary_min_len {
+            args.dictionary_min_len = min_len;
+        }
+        args.class_run = self.class_run.as_deref().map(parse_class_run).transpose()?;
+        args.exact_count = self.exact_count;
+        args.addresses_per_seed = self.addresses_per_seed;
+        args.coin_type = self.coin_type;
+        args.one_per_seed = self.one_per_seed;
+        Ok(())
+    }
+}
+
+/// One match: the seed's number in the sequence, the address, the matched pattern(s) and the
+/// address index
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestMatch {
+    seed: u64,
+    address: String,
+    pattern: String,
+    index: u32,
+}
+
+impl From<&SeedListMatch> for ManifestMatch {
+    fn from((seed, address, pattern, index): &SeedListMatch) -> Self {
+        Self {
+            seed: *seed as u64,
+            address: address.clone(),
+            pattern: pattern.clone(),
+            index: *index,
+        }
+    }
+}
+
+/// A `--seed-sequence` search covering seeds `first_seed..first_seed + seeds_checked`
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    version: u32,
+    /// Hex entropy the sequence is derived from (see `utils::deterministic_mnemonics`)
+    pub base_entropy: String,
+    pub first_seed: u64,
+    pub seeds_checked: u64,
+    criteria: Criteria,
+    matches: Vec<ManifestMatch>,
+}
+
+impl Manifest {
+    /// Records a search of `count` seeds from `first_seed` with the criteria of `args`
+    pub fn new(args: &Args, base_entropy: &str, first_seed: u64, count: usize, matches: &[SeedListMatch]) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            base_entropy: base_entropy.trim().to_ascii_lowercase(),
+            first_seed,
+            seeds_checked: count as u64,
+            criteria: Criteria::from_args(args),
+            matches: matches.iter().map(ManifestMatch::from).collect(),
+        }
+    }
+
+    /// Reads the manifest at `path` and applies its criteria to `args`
+    pub fn load(path: &Path, args: &mut Args) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let manifest: Self = serde_json::from_str(&data).map_err(|e| format!("{} is not a valid manifest: {}", path.display(), e))?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(format!(
+                "{} has manifest version {}, this build reads version {}",
+                path.display(),
+                manifest.version,
+                MANIFEST_VERSION
+            ));
+        }
+        manifest.criteria.apply(args).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(manifest)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, data + "\n").map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+
+    /// First seed not yet checked, where a resumed search continues
+    pub fn next_seed(&self) -> u64 {
+        self.first_seed + self.seeds_checked
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Adds the matches of `count` further seeds checked from `next_seed`
+    pub fn extend(&mut self, count: usize, matches: &[SeedListMatch]) {
+        self.seeds_checked += count as u64;
+        self.matches.extend(matches.iter().map(ManifestMatch::from));
+    }
+
+    /// Compares the matches of a replay of every seed with the recorded ones and describes
+    /// each one missing from either side
+    pub fn differences(&self, replayed: &[SeedListMatch]) -> Vec<String> {
+        let replayed: Vec<ManifestMatch> = replayed.iter().map(ManifestMatch::from).collect();
+        let describe = |m: &ManifestMatch| format!("seed {} index {}: {} ({})", m.seed, m.index, m.address, m.pattern);
+        let missing = self
+            .matches
+            .iter()
+            .filter(|m| !replayed.contains(m))
+            .map(|m| format!("recorded but not found: {}", describe(m)));
+        let unrecorded = replayed
+            .iter()
+            .filter(|m| !self.matches.contains(m))
+            .map(|m| format!("found but not recorded: {}", describe(m)));
+        missing.chain(unrecorded).collect()
+    }
+}
@@ -1,122 +1,992 @@
 /// Module for address pattern matching functionality.
 /// Extracts matcher logic from args.rs and address_processor.rs
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use aho_corasick::AhoCorasick;
+use crate::utils::{describe_chars, is_p2pk_start_char, p2pk_start_chars, AddressFormat};
+
+/// Where in the address a pattern must appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// At the start of the address (after the leading network character)
+    Start,
+    /// At the end of the address
+    End,
+    /// Anywhere in the address
+    Anywhere,
+    /// Anywhere within the last `CHECKSUM_CHARS` characters, which the checksum determines.
+    /// Purely cosmetic: those characters can't be chosen, so every one costs the full factor of 58
+    Checksum,
+}
+
+impl MatchMode {
+    /// Returns the mode selected by the global start/end flags.
+    pub fn from_flags(start: bool, end: bool) -> Self {
+        if start {
+            MatchMode::Start
+        } else if end {
+            MatchMode::End
+        } else {
+            MatchMode::Anywhere
+        }
+    }
+
+    /// Prefix used in the `mode:pattern` syntax.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            MatchMode::Start => "start",
+            MatchMode::End => "end",
+            MatchMode::Anywhere => "any",
+            MatchMode::Checksum => "checksum",
+        }
+    }
+}
+
+/// Parses a pattern spec of the form `start:efg`, `end:xyz`, `any:cafe` or `checksum:777`.
+///
+/// Specs without a recognised prefix use `default_mode`.
+/// Returns the mode, the bare pattern and whether the mode was given explicitly.
+pub fn parse_pattern_spec(spec: &str, default_mode: MatchMode) -> (MatchMode, String, bool) {
+    if let Some((prefix, pattern)) = spec.split_once(':') {
+        let mode = match prefix.trim().to_lowercase().as_str() {
+            "start" => Some(MatchMode::Start),
+            "end" => Some(MatchMode::End),
+            "any" => Some(MatchMode::Anywhere),
+            "checksum" => Some(MatchMode::Checksum),
+            _ => None,
+        };
+        if let Some(mode) = mode {
+            return (mode, pattern.trim().to_string(), true);
+        }
+    }
+    (default_mode, spec.trim().to_string(), false)
+}
+
+/// Characters rejected by `--clean` unless `--clean-chars` says otherwise:
+/// ones easily misread even within Base58 (`1` against `i`/`j`).
+pub const DEFAULT_CLEAN_CHARS: &str = "1ij";
+
+/// Number of trailing address characters determined by the 4-byte checksum
+/// (32 bits span ~5.5 Base58 characters).
+pub const CHECKSUM_CHARS: usize = 6;
+
+/// Longest start pattern that fits in the variable region of an address:
+/// everything after the leading network character and before the checksum tail.
+pub fn max_start_pattern_len() -> usize {
+    crate::utils::p2pk_address_length() - 1 - CHECKSUM_CHARS
+}
+
+/// Levenshtein edit distance between two strings.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr = vec![0; b_chars.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b_chars.len()]
+}
+
+/// One position of a `--shape` template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeClass {
+    /// `L`: any letter
+    Letter,
+    /// `D`: any digit
+    Digit,
+    /// Any other character matches itself
+    Literal(char),
+}
+
+impl ShapeClass {
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            ShapeClass::Letter => c.is_ascii_alphabetic(),
+            ShapeClass::Digit => c.is_ascii_digit(),
+            ShapeClass::Literal(l) => *l == c,
+        }
+    }
+}
+
+/// Parses a shape template such as `LLDDLL`: `L` is any letter, `D` any digit and every
+/// other character a literal (lowercased unless matching is case-sensitive).
+pub fn parse_shape(template: &str, case_sensitive: bool) -> Vec<ShapeClass> {
+    template
+        .chars()
+        .map(|c| match c {
+            'L' => ShapeClass::Letter,
+            'D' => ShapeClass::Digit,
+            c if case_sensitive => ShapeClass::Literal(c),
+            c => ShapeClass::Literal(c.to_ascii_lowercase()),
+        })
+        .collect()
+}
+
+/// Whether `window` follows `shape` position by position (and has the same length)
+pub fn match_shape(shape: &[ShapeClass], window: &str) -> bool {
+    window.chars().count() == shape.len() && shape.iter().zip(window.chars()).all(|(class, c)| class.accepts(c))
+}
+
+/// Number of positions in `window` that break `shape`, for near-miss reporting
+fn shape_mismatches(shape: &[ShapeClass], window: &str) -> usize {
+    let matched = shape.iter().zip(window.chars()).filter(|(class, c)| class.accepts(*c)).count();
+    shape.len() - matched
+}
+
+/// Characters a `--class-run` requires, without saying which ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CharClass {
+    /// Any digit (9 in Base58, which has no `0`)
+    Digit,
+    /// Any uppercase letter (24 in Base58, which has no `I` or `O`)
+    Upper,
+    /// Any lowercase letter (25 in Base58, which has no `l`)
+    Lower,
+}
+
+impl CharClass {
+    pub fn accepts(&self, c: char) -> bool {
+        match self {
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Upper => c.is_ascii_uppercase(),
+            CharClass::Lower => c.is_ascii_lowercase(),
+        }
+    }
+
+    /// Name used in `--class-run` and in the reported match label
+    pub fn name(&self) -> &'static str {
+        match self {
+            CharClass::Digit => "digit",
+            CharClass::Upper => "upper",
+            CharClass::Lower => "lower",
+        }
+    }
+
+    /// Plural description for messages, e.g. "digits"
+    pub fn description(&self) -> &'static str {
+        match self {
+            CharClass::Digit => "digits",
+            CharClass::Upper => "uppercase letters",
+            CharClass::Lower => "lowercase letters",
+        }
+    }
+
+    /// Number of characters of `format` in this class
+    pub fn size(&self, format: AddressFormat) -> usize {
+        format.charset().chars().filter(|&c| self.accepts(c)).count()
+    }
+}
+
+/// Byte offset of the first character a `--class-run` covers: the leading '9' and the
+/// character after it (see `utils::p2pk_start_chars`) are fixed by the network and key prefix.
+pub const CLASS_RUN_OFFSET: usize = 2;
+
+/// Parses a `--class-run` spec such as `4:digit` into its length and class.
+pub fn parse_class_run(spec: &str) -> Result<(usize, CharClass), String> {
+    let (len, class) = spec
+        .split_once(':')
+        .ok_or_else(|| format!("expected N:digit|upper|lower, got '{}'", spec))?;
+    let len: usize = len
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a run length", len.trim()))?;
+    if len == 0 || len > max_start_pattern_len() - 1 {
+        return Err(format!("the run length must be between 1 and {}", max_start_pattern_len() - 1));
+    }
+    let class = <CharClass as clap::ValueEnum>::from_str(class.trim(), true)
+        .map_err(|_| format!("unknown class '{}' (expected digit, upper or lower)", class.trim()))?;
+    Ok((len, class))
+}
+
+/// A single pattern together with the mode it is matched under.
+struct PatternEntry {
+    mode: MatchMode,
+    pattern: String,
+    // Reported as the matched pattern; carries the mode prefix when one was given
+    label: String,
+    // Cleared once the pattern no longer needs to be tested (e.g. its balanced quota is met)
+    active: AtomicBool,
+    // Set when the pattern is a `--shape` template rather than a literal
+    shape: Option<Vec<ShapeClass>>,
+    // Found through the matcher's `AnywhereIndex` instead of by `matches`
+    indexed: bool,
+}
+
+impl PatternEntry {
+    /// Whether an already normalized address matches this pattern under its mode.
+    /// End patterns ignore the last `end_skip` characters.
+    fn matches(&self, address: &str, end_skip: usize) -> bool {
+        self.find(address, end_skip).is_some()
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Byte offset and length of the first place this pattern matches in an already
+    /// normalized address, if any
+    fn find(&self, address: &str, end_skip: usize) -> Option<(usize, usize)> {
+        let address = if self.mode == MatchMode::End {
+            &address[..address.len().saturating_sub(end_skip)]
+        } else {
+            address
+        };
+        // Checksum patterns only look at the tail, but report offsets in the whole address
+        let tail_start = address.len().saturating_sub(CHECKSUM_CHARS);
+        if let Some(shape) = &self.shape {
+            let len = shape.len();
+            let offset = match self.mode {
+                MatchMode::Start => address.get(1..1 + len).filter(|w| match_shape(shape, w)).map(|_| 1),
+                MatchMode::End => (address.len() >= len && match_shape(shape, &address[address.len() - len..]))
+                    .then(|| address.len() - len),
+                MatchMode::Anywhere => (0..=address.len().saturating_sub(len))
+                    .find(|&i| address.get(i..i + len).is_some_and(|w| match_shape(shape, w))),
+                MatchMode::Checksum => (tail_start..=address.len().saturating_sub(len))
+                    .find(|&i| address.get(i..i + len).is_some_and(|w| match_shape(shape, w))),
+            };
+            return offset.map(|offset| (offset, len));
+        }
+        let offset = match self.mode {
+            MatchMode::Start => (address.len() > 1 && address[1..].starts_with(&self.pattern)).then_some(1),
+            MatchMode::End => address.ends_with(&self.pattern).then(|| address.len() - self.pattern.len()),
+            MatchMode::Anywhere => address.find(&self.pattern),
+            MatchMode::Checksum => address[tail_start..].find(&self.pattern).map(|offset| tail_start + offset),
+        };
+        offset.map(|offset| (offset, self.pattern.len()))
+    }
+
+    /// Number of places, overlapping ones included, where this anywhere pattern occurs in an
+    /// already normalized address
+    fn occurrences(&self, address: &str) -> usize {
+        let len = self.shape.as_ref().map_or(self.pattern.len(), |shape| shape.len());
+        (0..=address.len().saturating_sub(len))
+            .filter(|&i| match (&self.shape, address.get(i..i + len)) {
+                (Some(shape), Some(window)) => match_shape(shape, window),
+                (None, Some(window)) => window == self.pattern,
+                (_, None) => false,
+            })
+            .count()
+    }
+}
+
+/// Automaton over the literal anywhere patterns, so any number of them is found in a single
+/// pass over the address instead of one substring search per pattern
+struct AnywhereIndex {
+    automaton: AhoCorasick,
+    // Position in `PatternMatcher::patterns` of each automaton pattern
+    entries: Vec<usize>,
+}
+
+impl AnywhereIndex {
+    /// Indexes the literal anywhere patterns among `patterns` and marks them as indexed
+    fn build(patterns: &mut [PatternEntry], case_sensitive: bool) -> Option<Self> {
+        let entries: Vec<usize> = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.mode == MatchMode::Anywhere && e.shape.is_none() && !e.pattern.is_empty())
+            .map(|(i, _)| i)
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+        let automaton = AhoCorasick::builder()
+            .ascii_case_insensitive(!case_sensitive)
+            .build(entries.iter().map(|&i| &patterns[i].pattern))
+            .expect("patterns form a valid automaton");
+        for &i in &entries {
+            patterns[i].indexed = true;
+        }
+        Some(Self { automaton, entries })
+    }
+
+    /// Positions in `patterns` of every indexed pattern found in the address (with repeats)
+    fn hits<'a>(&'a self, address: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.automaton
+            .find_overlapping_iter(address)
+            .map(|m| self.entries[m.pattern().as_usize()])
+    }
+}
+
+/// Words matched anywhere in the address by `--dictionary`, scanned all at once
+#[cfg(feature = "dictionary")]
+struct Dictionary {
+    automaton: AhoCorasick,
+    words: Vec<String>,
+}
+
 pub struct PatternMatcher {
-    patterns: Vec<String>,
+    patterns: Vec<PatternEntry>,
     case_sensitive: bool,
-    start: bool,
-    end: bool,
+    // Encoding of the addresses being matched; determines which pattern characters are valid
+    format: AddressFormat,
+    // Labels of patterns dropped because they repeated an earlier one
+    duplicates: Vec<String>,
+    // Patterns are shape templates (`L` letter, `D` digit) instead of literals
+    shape: bool,
+    // Addresses containing any of these characters are rejected even if a pattern matches
+    excluded_chars: Vec<char>,
+    // End patterns match just before the checksum tail instead of at the very end
+    end_before_checksum: bool,
+    // Literal anywhere patterns, matched together in one scan
+    anywhere: Option<AnywhereIndex>,
+    // Dictionary words that count as matches too, reported as the word found
+    #[cfg(feature = "dictionary")]
+    dictionary: Option<Dictionary>,
+    // Run of characters of one class after the address prefix that counts as a match too
+    class_run: Option<(usize, CharClass)>,
+    // Anywhere patterns must occur exactly this many times
+    exact_count: Option<usize>,
 }
 
 impl PatternMatcher {
     /// Create a new PatternMatcher.
+    /// Every pattern is matched under the mode selected by the global start/end flags,
+    /// unless it carries an explicit `start:`, `end:` or `any:` prefix.
     /// If case_sensitive is false, all patterns are converted to lowercase.
     pub fn new(patterns: Vec<String>, case_sensitive: bool, start: bool, end: bool) -> Self {
-        // Patterns will be validated in the GUI, no validation here for real-time checking
+        let default_mode = MatchMode::from_flags(start, end);
+        let specs = patterns
+            .iter()
+            .map(|p| parse_pattern_spec(p, default_mode))
+            .collect();
+        Self::with_modes(specs, case_sensitive)
+    }
 
-        // Convert to lowercase if case insensitive
-        let final_patterns = if !case_sensitive {
-            patterns.into_iter().map(|p| p.to_lowercase()).collect()
-        } else {
-            patterns
-        };
+    /// Create a PatternMatcher from `(mode, pattern, explicit)` entries as returned by
+    /// `parse_pattern_spec`.
+    /// Patterns that repeat an earlier one (same mode, after case folding) are dropped and
+    /// reported by `duplicates`.
+    pub fn with_modes(specs: Vec<(MatchMode, String, bool)>, case_sensitive: bool) -> Self {
+        Self::with_options(specs, case_sensitive, false)
+    }
+
+    /// Like `with_modes`; when `shape` is set every pattern is a shape template
+    /// (see `parse_shape`) and matched per position with `match_shape`.
+    pub fn with_options(specs: Vec<(MatchMode, String, bool)>, case_sensitive: bool, shape: bool) -> Self {
+        // Patterns will be validated in the GUI, no validation here for real-time checking
+        let mut patterns: Vec<PatternEntry> = Vec::new();
+        let mut duplicates = Vec::new();
+        for (mode, pattern, explicit) in specs {
+            // Convert to lowercase if case insensitive; shape templates keep their L/D classes
+            let pattern = if case_sensitive || shape { pattern } else { pattern.to_lowercase() };
+            let label = if explicit {
+                format!("{}:{}", mode.prefix(), pattern)
+            } else {
+                pattern.clone()
+            };
+            if patterns.iter().any(|e| e.mode == mode && e.pattern == pattern) {
+                duplicates.push(label);
+                continue;
+            }
+            let classes = shape.then(|| parse_shape(&pattern, case_sensitive));
+            patterns.push(PatternEntry { mode, pattern, label, active: AtomicBool::new(true), shape: classes, indexed: false });
+        }
+        let anywhere = AnywhereIndex::build(&mut patterns, case_sensitive);
 
         Self {
-            patterns: final_patterns,
+            patterns,
+            anywhere,
             case_sensitive,
-            start,
-            end,
+            format: AddressFormat::default(),
+            duplicates,
+            shape,
+            excluded_chars: Vec::new(),
+            end_before_checksum: false,
+            #[cfg(feature = "dictionary")]
+            dictionary: None,
+            class_run: None,
+            exact_count: None,
+        }
+    }
+
+    /// Rejects matching addresses that contain any of `chars` anywhere, for more legible
+    /// addresses. Characters are compared exactly, regardless of case sensitivity.
+    pub fn set_excluded_chars(&mut self, chars: &str) {
+        self.excluded_chars = chars.chars().collect();
+    }
+
+    /// Makes end patterns match the characters just before the last `CHECKSUM_CHARS`,
+    /// i.e. the end of the portion not dominated by the checksum. The odds are the same
+    /// as for a plain suffix; only the position changes.
+    pub fn set_end_before_checksum(&mut self, enabled: bool) {
+        self.end_before_checksum = enabled;
+    }
+
+    /// Also matches any word of at least `min_len` letters from the embedded dictionary (the
+    /// 2048-word BIP39 English list) anywhere in the address, reporting the word as the
+    /// pattern. Words with characters that can never appear in an address are left out.
+    /// Does nothing when built without the `dictionary` feature.
+    pub fn set_dictionary(&mut self, min_len: usize) {
+        #[cfg(feature = "dictionary")]
+        {
+            let words: Vec<String> = bip39::Language::English
+                .word_list()
+                .iter()
+                .filter(|word| word.len() >= min_len)
+                .filter(|word| word.chars().all(|c| {
+                    self.format.is_valid_char(c) || (!self.case_sensitive && self.format.is_valid_char(c.to_ascii_uppercase()))
+                }))
+                .map(|word| word.to_string())
+                .collect();
+            let automaton = AhoCorasick::builder()
+                .match_kind(aho_corasick::MatchKind::LeftmostLongest)
+                .build(&words)
+                .expect("dictionary words form a valid automaton");
+            self.dictionary = Some(Dictionary { automaton, words });
+        }
+        #[cfg(not(feature = "dictionary"))]
+        let _ = min_len;
+    }
+
+    /// Number of dictionary words that count as matches (0 without `set_dictionary`)
+    pub fn dictionary_size(&self) -> usize {
+        #[cfg(feature = "dictionary")]
+        {
+            self.dictionary.as_ref().map_or(0, |d| d.words.len())
+        }
+        #[cfg(not(feature = "dictionary"))]
+        {
+            0
+        }
+    }
+
+    /// Longest dictionary word in the (normalized) address, leftmost on ties, with its byte offset
+    fn dictionary_word(&self, address: &str) -> Option<(usize, String)> {
+        #[cfg(feature = "dictionary")]
+        {
+            let dictionary = self.dictionary.as_ref()?;
+            dictionary.automaton
+                .find_iter(address)
+                .max_by_key(|m| (m.len(), std::cmp::Reverse(m.start())))
+                .map(|m| (m.start(), dictionary.words[m.pattern().as_usize()].clone()))
         }
+        #[cfg(not(feature = "dictionary"))]
+        {
+            let _ = address;
+            None
+        }
+    }
+
+    /// Also counts an address as a match when its `len` characters after the prefix (from
+    /// `CLASS_RUN_OFFSET`) all belong to `class`, reported as e.g. `digit:7382`. The class is
+    /// checked on the address as is, whatever the case sensitivity.
+    pub fn set_class_run(&mut self, len: usize, class: CharClass) {
+        self.class_run = Some((len, class));
+    }
+
+    /// Requires anywhere patterns to occur exactly `count` times in the address (overlapping
+    /// occurrences count separately), instead of at least once. Other modes are unaffected.
+    pub fn set_exact_count(&mut self, count: usize) {
+        self.exact_count = Some(count);
+    }
+
+    /// Whether an active, non-indexed entry matches an already normalized address,
+    /// including the `--exact-count` requirement for anywhere patterns
+    fn entry_matches(&self, entry: &PatternEntry, address: &str) -> bool {
+        !entry.indexed
+            && entry.is_active()
+            && entry.matches(address, self.end_skip())
+            && (entry.mode != MatchMode::Anywhere || self.exact_count.is_none_or(|count| entry.occurrences(address) == count))
+    }
+
+    /// Positions in `patterns` of the active indexed patterns found in an already normalized
+    /// address, sorted and deduplicated; with `--exact-count`, only those found that many times
+    fn indexed_hits(&self, address: &str) -> Vec<usize> {
+        let Some(index) = &self.anywhere else {
+            return Vec::new();
+        };
+        let mut hits: Vec<usize> = index.hits(address).filter(|&i| self.patterns[i].is_active()).collect();
+        hits.sort_unstable();
+        hits.chunk_by(|a, b| a == b)
+            .filter(|run| self.exact_count.is_none_or(|count| run.len() == count))
+            .map(|run| run[0])
+            .collect()
+    }
+
+    /// The characters covering the `--class-run`, if they all belong to its class
+    fn class_run_match<'a>(&self, address: &'a str) -> Option<&'a str> {
+        let (len, class) = self.class_run?;
+        address
+            .get(CLASS_RUN_OFFSET..CLASS_RUN_OFFSET + len)
+            .filter(|run| run.chars().all(|c| class.accepts(c)))
+    }
+
+    /// Label reported for an address matching the `--class-run`
+    fn class_run_label(&self, address: &str) -> Option<String> {
+        let (_, class) = self.class_run?;
+        self.class_run_match(address).map(|run| format!("{}:{}", class.name(), run))
+    }
+
+    /// Trailing characters end patterns skip
+    fn end_skip(&self) -> usize {
+        if self.end_before_checksum { CHECKSUM_CHARS } else { 0 }
+    }
+
+    /// Whether the address contains a character excluded by `set_excluded_chars`
+    fn has_excluded_char(&self, address: &str) -> bool {
+        !self.excluded_chars.is_empty() && address.chars().any(|c| self.excluded_chars.contains(&c))
+    }
+
+    /// Labels of duplicate patterns that were dropped
+    pub fn duplicates(&self) -> &[String] {
+        &self.duplicates
+    }
+
+    /// The address encoding this matcher expects
+    pub fn format(&self) -> AddressFormat {
+        self.format
     }
 
     /// Validate that at least one pattern exists.
-    /// For start matching, ensure that each pattern starts with one of: e, f, g, h, i.
-    /// Also validate that all patterns only contain valid Base58 characters.
+    /// For start matching, ensure that each pattern starts with a valid second address
+    /// character (e, f, g, h or i, see `utils::p2pk_start_chars`).
+    /// Also validate that all patterns only contain characters of the address format.
     pub fn validate(&self) -> Result<(), String> {
-        if self.patterns.is_empty() {
+        if self.patterns.is_empty() && self.dictionary_size() == 0 && self.class_run.is_none() {
             return Err("At least one pattern must be specified".to_string());
         }
 
-        // For "start" pattern, must be a valid second character (check after case conversion)
-        if self.start {
-            for pat in &self.patterns {
-                if !pat.is_empty() {
-                    let first_char = pat.chars().next().unwrap();
-                    if !['e', 'f', 'g', 'h', 'i'].contains(&first_char) {
-                        return Err(format!("Invalid start pattern '{}'. Start patterns must begin with e, f, g, h, or i", pat));
-                    }
+        if let Some(error) = self.patterns.iter().find_map(|entry| self.pattern_error(entry)) {
+            return Err(error);
+        }
+
+        if self.exact_count.is_some() && !self.patterns.iter().any(|e| e.mode == MatchMode::Anywhere) {
+            return Err("--exact-count only applies to anywhere patterns (drop --start/--end or use an any: prefix)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Every pattern `validate` rejects, with the reason, plus empty patterns (e.g. from a
+    /// trailing comma), which match every address. Used by `--strict-patterns` to list all
+    /// bad patterns at once.
+    pub fn pattern_errors(&self) -> Vec<String> {
+        self.patterns
+            .iter()
+            .filter_map(|entry| {
+                if entry.pattern.is_empty() {
+                    Some(format!("Pattern '{}' is empty and would match every address", entry.label))
+                } else {
+                    self.pattern_error(entry)
                 }
+            })
+            .collect()
+    }
+
+    /// Why `entry` can never match, if it can't: characters outside the address alphabet or
+    /// rejected by --clean, or a start or checksum pattern that doesn't fit its position
+    fn pattern_error(&self, entry: &PatternEntry) -> Option<String> {
+        // Case-insensitive patterns are lowercased, so accept a character if either case is valid
+        let invalid: String = entry.pattern.chars()
+            .filter(|&c| !(entry.shape.is_some() && (c == 'L' || c == 'D')))
+            .filter(|&c| {
+                !self.format.is_valid_char(c)
+                    && (self.case_sensitive || !self.format.is_valid_char(c.to_ascii_uppercase()))
+            })
+            .collect();
+        if !invalid.is_empty() {
+            return Some(format!(
+                "Pattern '{}' contains characters that never appear in {:?} addresses: {}",
+                entry.label, self.format, invalid
+            ));
+        }
+        if let Some(c) = entry.pattern.chars().find(|c| self.excluded_chars.contains(c)) {
+            return Some(format!("Pattern '{}' contains '{}', which --clean rejects", entry.label, c));
+        }
+
+        match entry.mode {
+            // Checksum patterns must fit in the checksum tail
+            MatchMode::Checksum if entry.pattern.len() > CHECKSUM_CHARS => Some(format!(
+                "Checksum pattern '{}' is {} characters long, but the checksum tail is only the last {} characters",
+                entry.pattern, entry.pattern.len(), CHECKSUM_CHARS
+            )),
+            MatchMode::Start => self.start_pattern_error(entry),
+            _ => None,
+        }
+    }
+
+    /// For "start" patterns, the first character must be a valid second address character
+    fn start_pattern_error(&self, entry: &PatternEntry) -> Option<String> {
+        let start_chars = p2pk_start_chars();
+        let first_char = match entry.shape.as_ref().and_then(|s| s.first()) {
+            Some(ShapeClass::Letter) if !start_chars.iter().any(char::is_ascii_alphabetic) => {
+                return Some(format!("Invalid start shape '{}'. The second address character is never a letter", entry.pattern));
+            }
+            Some(ShapeClass::Digit) if !start_chars.iter().any(char::is_ascii_digit) => {
+                return Some(format!("Invalid start shape '{}'. The second address character is never a digit", entry.pattern));
+            }
+            Some(ShapeClass::Letter | ShapeClass::Digit) => None,
+            _ => entry.pattern.chars().next(),
+        };
+        if let Some(first_char) = first_char {
+            if !is_p2pk_start_char(first_char, self.case_sensitive) {
+                return Some(format!(
+                    "Invalid start pattern '{}'. Start patterns must begin with {}",
+                    entry.pattern,
+                    describe_chars(start_chars)
+                ));
             }
         }
 
-        // Note: Base58 validation is now done in the constructor before case conversion
-        Ok(())
+        // Characters past the variable region are fixed by the checksum
+        let max_len = max_start_pattern_len();
+        (entry.pattern.len() > max_len).then(|| format!(
+            "Start pattern '{}' is {} characters long, but only the first {} characters after the leading '9' can be chosen; the rest are determined by the checksum",
+            entry.pattern, entry.pattern.len(), max_len
+        ))
+    }
+
+    /// Returns warnings for dropped duplicate patterns and for patterns that are valid but
+    /// infeasible in practice, including their estimated attempt counts.
+    pub fn warnings(&self) -> Vec<String> {
+        let address_length = crate::utils::p2pk_address_length();
+        let duplicates = self.duplicates
+            .iter()
+            .map(|label| format!("Duplicate pattern '{}' ignored", label));
+        let infeasible = self.patterns
+            .iter()
+            .filter_map(|entry| {
+                let estimate = crate::estimator::estimate(&entry.pattern, entry.mode, self.shape, address_length, self.exact_count);
+                if estimate.is_infeasible() {
+                    Some(format!(
+                        "Pattern '{}' needs ~{:.3e} attempts and is infeasible in practice",
+                        entry.label, estimate.attempts_needed
+                    ))
+                } else {
+                    None
+                }
+            });
+        // Every free position must avoid the excluded characters
+        let clean = (!self.excluded_chars.is_empty()).then(|| {
+            let alphabet = self.format.charset().len() as f64;
+            let allowed = self.format.charset().chars().filter(|c| !self.excluded_chars.contains(c)).count() as f64;
+            let factor = (alphabet / allowed).powi(address_length as i32 - 2);
+            format!("--clean rejects most matches; expect the search to take about {:.0}x longer", factor)
+        });
+        duplicates.chain(infeasible).chain(clean).collect()
+    }
+
+    /// Number of patterns in the matcher
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Stops testing the pattern reported as `label`, narrowing the active pattern set.
+    /// Returns true if it was active.
+    pub fn deactivate(&self, label: &str) -> bool {
+        self.patterns
+            .iter()
+            .filter(|e| e.label == label)
+            .any(|e| e.active.swap(false, Ordering::Relaxed))
     }
 
-    /// Check if matcher has multiple patterns to balance across
-    pub fn has_multiple_patterns(&self) -> bool {
-        self.patterns.len() > 1
+    /// Whether any pattern is still being tested
+    pub fn has_active_patterns(&self) -> bool {
+        self.active_entries().next().is_some()
     }
 
-    /// Checks whether the given address matches any pattern.
-    /// If start matching is enabled, it checks the substring after the first character.
-    /// Otherwise, it either checks for an ending match or an anywhere match.
+    fn active_entries(&self) -> impl Iterator<Item = &PatternEntry> {
+        self.patterns.iter().filter(|e| e.is_active())
+    }
+
+    /// Positions in `patterns` of the active patterns matching an already normalized address,
+    /// in pattern order. Indexed anywhere patterns come from one automaton scan; the rest
+    /// (start, end and shape patterns) are checked one by one.
+    fn matching_entries(&self, address: &str) -> Vec<usize> {
+        let mut hits: Vec<usize> = self.patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.entry_matches(e, address))
+            .map(|(i, _)| i)
+            .chain(self.indexed_hits(address))
+            .collect();
+        hits.sort_unstable();
+        hits
+    }
+
+    /// Checks whether the given address matches any active pattern.
+    /// Each pattern is checked under its own mode: start patterns are compared against the
+    /// substring after the first character, end patterns against the end of the address
+    /// (or the end before the checksum tail, see `set_end_before_checksum`),
+    /// and anywhere patterns against the whole address.
+    /// When several patterns match, the one listed first wins.
+    #[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
     pub fn is_match(&self, address: &str) -> Option<String> {
-        if self.start {
-            self.match_start(address)
-        } else if self.end {
-            self.match_end(address)
-        } else {
-            self.match_anywhere(address)
+        let addr_to_check = self.normalize(address);
+        let indexed = match self.exact_count {
+            None => self.anywhere.as_ref().and_then(|index| {
+                index.hits(&addr_to_check).filter(|&i| self.patterns[i].is_active()).min()
+            }),
+            // Counting occurrences needs every hit, not just the first
+            Some(_) => self.indexed_hits(&addr_to_check).first().copied(),
+        };
+        // Only patterns listed before the first indexed hit can take precedence over it
+        let direct = self.patterns
+            .iter()
+            .take(indexed.unwrap_or(self.patterns.len()))
+            .position(|e| self.entry_matches(e, &addr_to_check));
+        direct
+            .or(indexed)
+            .map(|i| self.patterns[i].label.clone())
+            .or_else(|| self.dictionary_word(&addr_to_check).map(|(_, word)| word))
+            .or_else(|| self.class_run_label(address))
+            .filter(|_| !self.has_excluded_char(address))
+    }
+
+    /// Like `is_match`, but returns every active pattern the address matches
+    /// (e.g. an address containing two different anywhere patterns).
+    #[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
+    pub fn matches_all(&self, address: &str) -> Vec<String> {
+        let addr_to_check = self.normalize(address);
+        let labels: Vec<String> = self.matching_entries(&addr_to_check)
+            .into_iter()
+            .map(|i| self.patterns[i].label.clone())
+            .chain(self.dictionary_word(&addr_to_check).map(|(_, word)| word))
+            .chain(self.class_run_label(address))
+            .collect();
+        if !labels.is_empty() && self.has_excluded_char(address) {
+            return Vec::new();
+        }
+        labels
+    }
+
+    /// Byte offset and length of the part of `address` matched by the pattern reported as
+    /// `label` (the first one, for a multi-pattern label like "abc, xyz"). Patterns count
+    /// even after they stopped being tested, so results can be highlighted after a search.
+    pub fn match_span(&self, address: &str, label: &str) -> Option<(usize, usize)> {
+        let label = label.split(", ").next().unwrap_or(label);
+        if self.class_run_label(address).as_deref() == Some(label) {
+            return self.class_run_match(address).map(|run| (CLASS_RUN_OFFSET, run.len()));
+        }
+        let address = self.normalize(address);
+        match self.patterns.iter().find(|e| e.label == label) {
+            Some(entry) => entry.find(&address, self.end_skip()),
+            // A dictionary word: the longest one found is the one reported
+            None => self.dictionary_word(&address).filter(|(_, word)| word == label).map(|(offset, word)| (offset, word.len())),
         }
     }
 
+    /// Returns the smallest edit distance between any pattern and the address region it
+    /// would have to match (the prefix, the suffix, or the closest window for anywhere
+    /// patterns), together with that pattern. Used to report near misses.
+    pub fn nearest(&self, address: &str) -> Option<(usize, String)> {
+        let addr = self.normalize(address);
+        self.active_entries()
+            .map(|entry| {
+                let len = entry.pattern.len();
+                // Shape templates count the positions that break the shape
+                let distance_to = |window: &str| match &entry.shape {
+                    Some(shape) => shape_mismatches(shape, window),
+                    None => levenshtein(&entry.pattern, window),
+                };
+                let distance = match entry.mode {
+                    MatchMode::Start => {
+                        let end = (1 + len).min(addr.len());
+                        distance_to(addr.get(1..end).unwrap_or(""))
+                    }
+                    MatchMode::End => {
+                        let end = addr.len().saturating_sub(self.end_skip());
+                        distance_to(&addr[end.saturating_sub(len)..end])
+                    }
+                    MatchMode::Anywhere => (0..=addr.len().saturating_sub(len))
+                        .map(|i| distance_to(&addr[i..(i + len).min(addr.len())]))
+                        .min()
+                        .unwrap_or(len),
+                    MatchMode::Checksum => (addr.len().saturating_sub(CHECKSUM_CHARS)..=addr.len().saturating_sub(len))
+                        .map(|i| distance_to(&addr[i..(i + len).min(addr.len())]))
+                        .min()
+                        .unwrap_or(len),
+                };
+                (distance, entry.label.clone())
+            })
+            .min_by_key(|(distance, _)| *distance)
+    }
+
     // Helper: Normalize the address string.
-    // If `skip_first` is true, the first character is skipped.
-    // Then, if case_sensitive is false, the string is lowercased.
-    fn normalize(&self, address: &str, skip_first: bool) -> String {
-        let s = if skip_first && address.len() > 1 {
-            address[1..].to_string()
-        } else {
-            address.to_string()
-        };
+    // If case_sensitive is false, the string is lowercased.
+    fn normalize(&self, address: &str) -> String {
         if self.case_sensitive {
-            s
+            address.to_string()
         } else {
-            s.to_lowercase()
+            address.to_lowercase()
         }
     }
+}
 
-    fn match_start(&self, address: &str) -> Option<String> {
-        if address.len() <= 1 {
-            return None;
-        }
-        let addr_to_check = self.normalize(address, true);
-        for pattern in &self.patterns {
-            if addr_to_check.starts_with(pattern) {
-                return Some(pattern.clone());
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn base58(len: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = String> {
+        let charset: Vec<char> = AddressFormat::default().charset().chars().collect();
+        prop::collection::vec(prop::sample::select(charset), len).prop_map(|chars| chars.into_iter().collect())
+    }
+
+    /// Up to three short patterns, so that plenty of cases match
+    fn specs() -> impl Strategy<Value = Vec<(MatchMode, String)>> {
+        let mode = prop::sample::select(vec![MatchMode::Start, MatchMode::End, MatchMode::Anywhere]);
+        prop::collection::vec((mode, base58(1..=3)), 1..=3)
+    }
+
+    /// Pattern specs mixing valid and invalid ones, and whether `--strict-patterns` must reject
+    /// each: an invalid Base58 character, a start pattern with an impossible first character, an
+    /// empty pattern and a checksum pattern longer than the checksum tail
+    const MIXED_PATTERNS: [(&str, bool); 7] = [
+        ("abc", false),
+        ("start:e", false),
+        ("end:xyz", false),
+        ("ab0", true),
+        ("start:a", true),
+        ("", true),
+        ("checksum:1234567", true),
+    ];
+
+    fn mixed_matcher(with_invalid: bool) -> PatternMatcher {
+        let patterns = MIXED_PATTERNS.iter().filter(|(_, bad)| with_invalid || !bad).map(|(p, _)| p.to_string()).collect();
+        PatternMatcher::new(patterns, false, false, false)
+    }
+
+    /// `pattern_errors` reports exactly the invalid patterns, one error each, and `validate`
+    /// fails on the set but passes on its valid patterns alone
+    #[test]
+    fn pattern_errors_reports_each_invalid_pattern() {
+        let errors = mixed_matcher(true).pattern_errors();
+        let invalid: Vec<&str> = MIXED_PATTERNS.iter().filter(|(_, bad)| *bad).map(|(p, _)| *p).collect();
+        for pattern in &invalid {
+            let bare = pattern.split_once(':').map_or(*pattern, |(_, bare)| bare);
+            assert!(
+                errors.iter().any(|e| e.contains(&format!("'{}'", bare)) || e.contains(&format!("'{}'", pattern))),
+                "'{}' is invalid but not reported in {:?}",
+                pattern,
+                errors
+            );
         }
-        None
+        assert_eq!(errors.len(), invalid.len(), "{:?}", errors);
+        assert!(mixed_matcher(true).validate().is_err());
+
+        let valid = mixed_matcher(false);
+        assert_eq!(valid.pattern_errors(), Vec::<String>::new());
+        assert!(valid.validate().is_ok());
     }
 
-    fn match_end(&self, address: &str) -> Option<String> {
-        let addr_to_check = self.normalize(address, false);
-        for pattern in &self.patterns {
-            if addr_to_check.ends_with(pattern) {
-                return Some(pattern.clone());
+    #[test]
+    fn duplicate_patterns_are_dropped_and_reported() {
+        let patterns = ["abc", "ABC", "end:abc", "start:e", "e"].iter().map(|p| p.to_string()).collect();
+        let matcher = PatternMatcher::new(patterns, false, false, false);
+        // "ABC" folds to "abc"; the end and start patterns differ in mode from the anywhere ones
+        assert_eq!(matcher.duplicates(), ["abc".to_string()]);
+        assert_eq!(matcher.pattern_count(), 4);
+        assert!(matcher.warnings().contains(&"Duplicate pattern 'abc' ignored".to_string()));
+
+        let matcher = PatternMatcher::new(vec!["abc".to_string(), "ABC".to_string()], true, false, false);
+        assert!(matcher.duplicates().is_empty());
+    }
+
+    /// A 51-character address: the leading '9', `middle`, then '2' padding
+    fn address(middle: &str) -> String {
+        format!("9{}{}", middle, "2".repeat(50 - middle.len()))
+    }
+
+    fn exact_matcher(patterns: &[&str], count: usize) -> PatternMatcher {
+        let mut matcher = PatternMatcher::new(patterns.iter().map(|p| p.to_string()).collect(), false, false, false);
+        matcher.set_exact_count(count);
+        matcher
+    }
+
+    #[test]
+    fn exact_count_requires_that_many_occurrences() {
+        let addresses = [address(""), address("xyz"), address("xyz3xyz")];
+        for (count, expected) in [(1, [false, true, false]), (2, [false, false, true])] {
+            let matcher = exact_matcher(&["xyz"], count);
+            for (address, expected) in addresses.iter().zip(expected) {
+                assert_eq!(matcher.is_match(address).is_some(), expected, "{} with --exact-count {}", address, count);
+                assert_eq!(!matcher.matches_all(address).is_empty(), expected, "{} with --exact-count {}", address, count);
             }
         }
-        None
     }
 
-    fn match_anywhere(&self, address: &str) -> Option<String> {
-        let addr_to_check = self.normalize(address, false);
-        for pattern in &self.patterns {
-            if addr_to_check.contains(pattern) {
-                return Some(pattern.clone());
+    #[test]
+    fn exact_count_includes_overlaps_and_ignores_other_modes() {
+        let matcher = exact_matcher(&["aa"], 2);
+        assert!(matcher.is_match(&address("aaa")).is_some());
+        assert!(matcher.is_match(&address("aa3aa3aa")).is_none());
+
+        // The start pattern matches regardless; the anywhere one occurs twice instead of once
+        let matcher = exact_matcher(&["start:xy", "any:xyz"], 1);
+        assert_eq!(matcher.matches_all(&address("xyz3xyz")), vec!["start:xy".to_string()]);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(20_000))]
+
+        /// The patterns `matches_all` reports are exactly those found by a plain `starts_with`
+        /// (after the leading character), `ends_with` or `contains`; `is_match` reports the first
+        /// of them; every `match_span` covers its pattern; and case-insensitive results don't
+        /// change when the address's letters change case
+        #[test]
+        fn matcher_agrees_with_a_plain_string_search(
+            case_sensitive: bool,
+            specs in specs(),
+            body in base58(50..=50),
+            planted in prop::option::of((any::<prop::sample::Index>(), any::<prop::sample::Index>())),
+            upper in prop::collection::vec(any::<bool>(), 51),
+        ) {
+            let mut address: Vec<char> = std::iter::once('9').chain(body.chars()).collect();
+            if let Some((which, at)) = planted {
+                let (mode, pattern) = &specs[which.index(specs.len())];
+                let at = match mode {
+                    MatchMode::Start => 1,
+                    MatchMode::End => address.len() - pattern.len(),
+                    _ => 1 + at.index(address.len() - pattern.len() - 1),
+                };
+                for (i, c) in pattern.chars().enumerate() {
+                    address[at + i] = c;
+                }
+            }
+            let address: String = address.into_iter().collect();
+            let matcher = PatternMatcher::with_modes(
+                specs.iter().map(|(mode, pattern)| (*mode, pattern.clone(), true)).collect(),
+                case_sensitive,
+            );
+
+            let fold = |s: &str| if case_sensitive { s.to_string() } else { s.to_lowercase() };
+            let folded = fold(&address);
+            let mut expected: Vec<String> = Vec::new();
+            for (mode, pattern) in &specs {
+                let pattern = fold(pattern);
+                let label = format!("{}:{}", mode.prefix(), pattern);
+                let hit = match mode {
+                    MatchMode::Start => folded[1..].starts_with(&pattern),
+                    MatchMode::End => folded.ends_with(&pattern),
+                    _ => folded.contains(&pattern),
+                };
+                if hit && !expected.contains(&label) {
+                    expected.push(label);
+                }
+            }
+
+            let found = matcher.matches_all(&address);
+            prop_assert_eq!(&found, &expected);
+            prop_assert_eq!(matcher.is_match(&address), found.first().cloned());
+            for label in &found {
+                let pattern = label.split_once(':').map_or(label.as_str(), |(_, p)| p);
+                let span = matcher.match_span(&address, label);
+                prop_assert_eq!(span.and_then(|(offset, len)| folded.get(offset..offset + len)), Some(pattern));
+            }
+            if !case_sensitive {
+                let recased: String = address
+                    .chars()
+                    .zip(&upper)
+                    .map(|(c, &upper)| if upper { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() })
+                    .collect();
+                prop_assert_eq!(matcher.matches_all(&recased), found);
             }
         }
-        None
     }
 }
@@ -0,0 +1,71 @@
+//! Provenance details that exporters can attach to a result.
+
+use chrono::Utc;
+use crate::utils::derivation_path_string;
+
+/// Network of every address this tool generates
+const NETWORK: &str = "mainnet";
+
+/// Longest search label kept by `sanitize_label`
+const MAX_LABEL_LEN: usize = 64;
+
+/// Reduces a user-given search label to ASCII letters, digits, `-`, `_` and `.` (spaces
+/// become `-`), at most 64 characters, so it can be embedded in HTML, JSON and file names
+/// as is. Returns `None` if nothing is left.
+pub fn sanitize_label(label: &str) -> Option<String> {
+    let sanitized: String = label
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .take(MAX_LABEL_LEN)
+        .collect();
+    Some(sanitized).filter(|s| !s.is_empty())
+}
+
+/// When and how a result was generated, shared by the event socket and the paper wallet
+pub struct ResultMetadata {
+    /// Generation time in UTC (RFC 3339)
+    pub generated_at: String,
+    pub tool_version: &'static str,
+    pub derivation_path: String,
+    pub network: &'static str,
+    pub word_count: usize,
+    /// Search label (`--label`), already sanitized
+    pub label: Option<String>,
+}
+
+impl ResultMetadata {
+    /// Metadata for the address at `position` of a `word_count`-word seed derived under
+    /// `coin_type`, stamped now
+    pub fn new(word_count: usize, position: u32, coin_type: u32) -> Self {
+        Self {
+            generated_at: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            tool_version: env!("CARGO_PKG_VERSION"),
+            derivation_path: derivation_path_string(coin_type, 0, position),
+            network: NETWORK,
+            word_count,
+            label: None,
+        }
+    }
+
+    /// Tags the metadata with the search label, which must come from `sanitize_label`
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "generated_at": self.generated_at,
+            "tool_version": self.tool_version,
+            "derivation_path": self.derivation_path,
+            "network": self.network,
+            "word_count": self.word_count,
+        });
+        if let Some(label) = &self.label {
+            json["label"] = serde_json::Value::from(label.as_str());
+        }
+        json
+    }
+}
@@ -1,8 +1,16 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
-use qrcode::QrCode;
+use qrcode::{EcLevel, QrCode};
 use chrono::Local;
+use zeroize::Zeroizing;
+use crate::metadata::ResultMetadata;
+use crate::utils::{derivation_path_string, generate_addresses, verify_mnemonic, AddressFormat, ERGO_COIN_TYPE};
+
+/// Detachable QR cards printed when the caller doesn't choose a number
+pub const DEFAULT_QR_CARDS: usize = 3;
+/// Most detachable QR cards that fit across the bottom of an A4 page
+pub const MAX_QR_CARDS: usize = 6;
 
 /// Information for generating a paper wallet
 pub struct PaperWalletInfo {
@@ -10,12 +18,26 @@ pub struct PaperWalletInfo {
     pub mnemonic: String,
     pub word_count: usize,
     pub position: u32,
+    /// Omit the plaintext seed words and expose the seed only as a QR code
+    pub qr_only_seed: bool,
+    /// Add a page with one small QR code per seed word, for backup tools that scan word by word
+    pub per_word_qr: bool,
+    /// Print a generation metadata block (time, version, path, network) on the wallet
+    pub metadata: Option<ResultMetadata>,
+    /// Print a restore checklist with the expected index-0 and matched-index addresses
+    pub restore_checklist: bool,
+    /// Number of detachable address QR cards along the bottom edge, up to `MAX_QR_CARDS`
+    pub qr_cards: usize,
+    /// Also decode the address QR codes back before saving; seed QR codes always are
+    pub verify_qr: bool,
 }
 
 /// Options for wallet encryption
 pub struct EncryptionOptions {
     pub encrypt_seed: bool,
     pub password_hint: Option<String>,
+    /// Password to encrypt with; prompted for on the terminal when not provided
+    pub password: Option<Zeroizing<String>>,
 }
 
 impl Default for EncryptionOptions {
@@ -23,6 +45,7 @@ impl Default for EncryptionOptions {
         Self {
             encrypt_seed: false,
             password_hint: None,
+            password: None,
         }
     }
 }
@@ -36,31 +59,60 @@ pub fn generate_paper_wallet(
     encryption_options: Option<EncryptionOptions>
 ) -> Result<(), String> {
     let encryption_options = encryption_options.unwrap_or_default();
-    let address_qr = generate_qr_code(&info.address, 150)?;
-    let small_qr = generate_qr_code(&info.address, 90)?;
+    let address_qr = generate_qr_code(&info.address, 150, info.verify_qr)?;
     
-    // Format the mnemonic for display (with numbered words)
-    let formatted_mnemonic = format_mnemonic(&info.mnemonic, info.word_count);
+    // Format the mnemonic for display (with numbered words), unless only the QR may carry it
+    let formatted_mnemonic = if info.qr_only_seed {
+        r#"<div class="encryption-note">The seed phrase is printed only as the QR code on this page.
+          The QR code still contains the secret: protect it exactly like the words themselves.</div>"#.to_string()
+    } else {
+        format_mnemonic(&info.mnemonic, info.word_count)
+    };
     
     // Handle seed phrase QR code and encryption if needed
     let (seed_qr, encryption_message) = if encryption_options.encrypt_seed {
-        let encrypted = encrypt_seed(&info.mnemonic)?;
+        let encrypted = encrypt_seed(&info.mnemonic, encryption_options.password.as_ref().map(|p| p.as_str()))?;
         let hint = encryption_options.password_hint
             .map(|h| format!("\nHint: {}", h))
             .unwrap_or_default();
         let qr_data = format!("ENCRYPTED:{}{}", encrypted, hint);
         (
-            generate_qr_code(&qr_data, 120)?,
+            generate_split_qr_html(&qr_data, 120)?,
             Some("This seed phrase is encrypted. Use your password to restore.")
         )
     } else {
-        (generate_qr_code(&info.mnemonic, 120)?, None)
+        (generate_split_qr_html(&info.mnemonic, 120)?, None)
     };
     
+    // Per-word codes hold the plain words, so they are left out of encrypted wallets
+    let word_qr_page = if !info.per_word_qr {
+        String::new()
+    } else if encryption_options.encrypt_seed {
+        log::warn!("Per-word QR codes omitted: they would bypass the seed encryption");
+        String::new()
+    } else {
+        generate_word_qr_page(&info.mnemonic)?
+    };
+
+    let metadata_block = info.metadata.as_ref().map(format_metadata).unwrap_or_default();
+    let restore_checklist = if info.restore_checklist {
+        format_restore_checklist(info)?
+    } else {
+        String::new()
+    };
     let current_date = Local::now().format("%Y-%m-%d").to_string();
-    let short_address = format!("{}...{}", 
-        &info.address[..8], 
-        &info.address[info.address.len().saturating_sub(6)..]);
+    let qr_cards = info.qr_cards.min(MAX_QR_CARDS);
+    let cards_section = format_qr_cards(&info.address, qr_cards, info.verify_qr)?;
+    // Without cards the folded wallet takes the whole page
+    let main_height = if qr_cards == 0 { 100 } else { 82 };
+    let card_width = (90 / qr_cards.max(1)).min(30);
+    let card_padding = if qr_cards > 3 { 5 } else { 10 };
+    let cut_step = if qr_cards == 0 {
+        String::new()
+    } else {
+        "<li>Cut along bottom edge to detach QR cards (3)</li>".to_string()
+    };
+    let cut_note = if qr_cards == 0 { "" } else { "<br>\n    • Cut along the bottom edge to detach QR cards" };
     
     let html = format!(r#"<!DOCTYPE html>
 <html lang="en">
@@ -107,7 +159,7 @@ pub fn generate_paper_wallet(
       display: grid;
       grid-template-columns: 1fr 1fr;
       grid-template-rows: 1fr 1fr;
-      height: 82%;
+      height: {main_height}%;
     }}
     
     /* ----- Quadrant 1: Public Address (Top Left) ----- */
@@ -301,8 +353,8 @@ pub fn generate_paper_wallet(
     .qr-card {{
       border: 1px solid #ddd;
       border-radius: 5px;
-      padding: 10px;
-      width: 30%;
+      padding: {card_padding}px;
+      width: {card_width}%;
       display: flex;
       flex-direction: column;
       align-items: center;
@@ -325,8 +377,9 @@ pub fn generate_paper_wallet(
       text-align: center;
     }}
     .qr-code-container {{
-      width: 90px;
-      height: 90px;
+      width: 100%;
+      max-width: 90px;
+      aspect-ratio: 1;
       margin: 0 auto;
       display: flex;
       justify-content: center;
@@ -442,6 +495,29 @@ pub fn generate_paper_wallet(
       left: 10px;
       bottom: -25px;
     }}
+
+    /* ----- Per-Word QR Page ----- */
+    .word-qr-page {{
+      page-break-before: always;
+      padding: 15mm;
+      display: block;
+    }}
+    .word-qr-grid {{
+      display: grid;
+      grid-template-columns: repeat(6, 1fr);
+      gap: 8px;
+      margin-top: 10px;
+    }}
+    .word-qr-card {{
+      border: 1px dashed #aaa;
+      border-radius: 4px;
+      padding: 6px;
+      text-align: center;
+    }}
+    .word-qr-card svg {{
+      width: 100%;
+      height: auto;
+    }}
   </style>
 </head>
 <body>
@@ -450,8 +526,7 @@ pub fn generate_paper_wallet(
     <strong>Quad-Fold Wallet Instructions:</strong><br>
     • Use thick, high-quality paper<br>
     • Print at 100% scale (no scaling)<br>
-    • Fold along the dashed lines in numbered order<br>
-    • Cut along the bottom edge to detach QR cards
+    • Fold along the dashed lines in numbered order{cut_note}
     <div class="fold-diagram">
       <div class="fold-diagram-inner">
         <div class="fold-diagram-quadrant fold-diagram-q1">Address</div>
@@ -492,6 +567,7 @@ pub fn generate_paper_wallet(
         <div style="font-size: 0.8em; color: #666; margin-top: 5px;">
           {word_count}-word seed • Path: m/44'/429'/0'/0/{position}
         </div>
+        {metadata_block}
         
         <div class="fold-instructions vertical">FOLD ALONG DASHED LINE</div>
       </div>
@@ -502,7 +578,7 @@ pub fn generate_paper_wallet(
         <ol style="margin-left: 16px;">
           <li>Print on high-quality paper</li>
           <li>Fold along both dashed lines in order (1, 2)</li>
-          <li>Cut along bottom edge to detach QR cards (3)</li>
+          {cut_step}
           <li>Keep this document in a safe place</li>
           <li>Never share your seed phrase with anyone</li>
         </ol>
@@ -515,6 +591,7 @@ pub fn generate_paper_wallet(
             3. Enter the exact seed phrase from inside<br>
             4. Verify the address matches this wallet
           </div>
+          {restore_checklist}
         </div>
         
         <div style="margin-top: 15px;">
@@ -563,38 +640,9 @@ pub fn generate_paper_wallet(
       </div>
     </div>
     
-    <!-- Detachable QR Cards Section -->
-    <div class="cards-section">
-      <div class="cards-divider">
-        <span class="scissors-icon">✂️ CUT HERE ✂️</span>
-      </div>
-      <div class="detachable-cards">
-        <div class="qr-card">
-          <div class="qr-card-title">ERGO WALLET</div>
-          <div class="qr-code-container">
-            {small_qr}
-          </div>
-          <div class="qr-card-address">{short_address}</div>
-        </div>
-        
-        <div class="qr-card">
-          <div class="qr-card-title">ERGO WALLET</div>
-          <div class="qr-code-container">
-            {small_qr}
-          </div>
-          <div class="qr-card-address">{short_address}</div>
-        </div>
-        
-        <div class="qr-card">
-          <div class="qr-card-title">ERGO WALLET</div>
-          <div class="qr-code-container">
-            {small_qr}
-          </div>
-          <div class="qr-card-address">{short_address}</div>
-        </div>
-      </div>
-    </div>
+    {qr_cards}
   </div>
+  {word_qr_page}
 </body>
 </html>"#,
         date = current_date,
@@ -603,8 +651,15 @@ pub fn generate_paper_wallet(
         word_count = info.word_count,
         address_qr = address_qr,
         seed_qr = seed_qr,
-        small_qr = small_qr,
-        short_address = short_address,
+        qr_cards = cards_section,
+        main_height = main_height,
+        card_width = card_width,
+        card_padding = card_padding,
+        cut_step = cut_step,
+        cut_note = cut_note,
+        word_qr_page = word_qr_page,
+        metadata_block = metadata_block,
+        restore_checklist = restore_checklist,
         mnemonic = formatted_mnemonic,
         encryption_message = encryption_message
           .map(|msg| format!(r#"<div class="encryption-note">{}</div>"#, msg))
@@ -620,6 +675,88 @@ pub fn generate_paper_wallet(
     Ok(())
 }
 
+/// Cut line and `count` identical detachable cards with the address QR code, or nothing
+/// for no cards. Cards narrow as the count grows so up to `MAX_QR_CARDS` fit on A4.
+fn format_qr_cards(address: &str, count: usize, verify: bool) -> Result<String, String> {
+    if count == 0 {
+        return Ok(String::new());
+    }
+    let small_qr = generate_qr_code(address, 90, verify)?;
+    let short_address = format!("{}...{}",
+        &address[..8],
+        &address[address.len().saturating_sub(6)..]);
+    let card = format!(r#"
+        <div class="qr-card">
+          <div class="qr-card-title">ERGO WALLET</div>
+          <div class="qr-code-container">
+            {small_qr}
+          </div>
+          <div class="qr-card-address">{short_address}</div>
+        </div>
+"#);
+    Ok(format!(r#"<!-- Detachable QR Cards Section -->
+    <div class="cards-section">
+      <div class="cards-divider">
+        <span class="scissors-icon">✂️ CUT HERE ✂️</span>
+      </div>
+      <div class="detachable-cards">{cards}      </div>
+    </div>"#, cards = card.repeat(count)))
+}
+
+/// Checklist of the addresses a restored wallet must show: index 0, which every wallet app
+/// lists first, and the matched index if it differs. Embedded as JSON too, like the metadata.
+fn format_restore_checklist(info: &PaperWalletInfo) -> Result<String, String> {
+    let first = generate_addresses(&info.mnemonic, 1, AddressFormat::default(), ERGO_COIN_TYPE)
+        .into_iter()
+        .next()
+        .ok_or("Could not derive the index-0 address for the restore checklist")?;
+
+    let mut checks = vec![(0, first.address)];
+    if info.position != 0 {
+        checks.push((info.position, info.address.clone()));
+    }
+    let items: String = checks
+        .iter()
+        .map(|(index, address)| {
+            format!(
+                r#"<div>☐ Address {index} ({path}):<br><span style="font-family: monospace; word-break: break-all;">{address}</span></div>"#,
+                path = derivation_path_string(ERGO_COIN_TYPE, 0, *index),
+            )
+        })
+        .collect();
+    let json = serde_json::json!(checks
+        .iter()
+        .map(|(index, address)| serde_json::json!({
+            "index": index,
+            "derivation_path": derivation_path_string(ERGO_COIN_TYPE, 0, *index),
+            "address": address,
+        }))
+        .collect::<Vec<_>>());
+
+    Ok(format!(
+        r#"<div style="font-size: 0.8em; margin-top: 6px;">
+            After restoring, tick off each address the wallet shows:
+            {items}
+          </div>
+          <script type="application/json" id="restore-checklist">{json}</script>"#
+    ))
+}
+
+/// Small human-readable metadata block, with the same fields embedded as JSON for tools
+fn format_metadata(metadata: &ResultMetadata) -> String {
+    format!(
+        r#"<div style="font-size: 0.75em; color: #888; margin-top: 3px;">
+          Generated {generated_at} • Ergo Vanitygen {version} • {network}{label}
+        </div>
+        <script type="application/json" id="wallet-metadata">{json}</script>"#,
+        generated_at = metadata.generated_at,
+        version = metadata.tool_version,
+        network = metadata.network,
+        label = metadata.label.as_ref().map(|l| format!(" • {}", l)).unwrap_or_default(),
+        json = metadata.to_json(),
+    )
+}
+
 /// Formats the mnemonic phrase with numbered words
 fn format_mnemonic(mnemonic: &str, _word_count: usize) -> String {
     let words: Vec<&str> = mnemonic.split_whitespace().collect();
@@ -635,31 +772,413 @@ fn format_mnemonic(mnemonic: &str, _word_count: usize) -> String {
     word_elements.join("\n")
 }
 
-/// Generates a QR code in SVG format
-fn generate_qr_code(data: &str, size: u32) -> Result<String, String> {
-    let qr = QrCode::new(data.as_bytes()).map_err(|e| e.to_string())?;
-    let svg = qr.render::<qrcode::render::svg::Color>()
+/// Generates a page with one QR code per seed word, each encoding "NN word".
+/// Cards are labelled by number only, so the words appear solely inside the codes.
+fn generate_word_qr_page(mnemonic: &str) -> Result<String, String> {
+    let cards = mnemonic
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            let qr = generate_qr_code(&format!("{:02} {}", i + 1, word), 70, true)?;
+            Ok(format!(
+                r#"<div class="word-qr-card">{}<div class="qr-label">Word {:02}</div></div>"#,
+                qr, i + 1
+            ))
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+
+    Ok(format!(
+        r#"<div class="wallet-container word-qr-page">
+    <h2>Seed Words as QR Codes</h2>
+    <div class="qr-label">PRIVATE: each code contains one seed word and its number. Protect this page like the seed phrase.</div>
+    <div class="word-qr-grid">
+      {}
+    </div>
+  </div>"#,
+        cards.join("\n      ")
+    ))
+}
+
+/// Maximum number of QR codes a payload may be split across before giving up
+const MAX_QR_PARTS: usize = 8;
+
+/// Pixels per module and quiet zone width (in modules) of the image a QR code is decoded
+/// from when it is verified
+const VERIFY_MODULE_PIXELS: u32 = 4;
+const VERIFY_QUIET_ZONE: usize = 4;
+
+/// Error for a payload that fits in no QR code, or none that decodes back
+const QR_FAILED: &str = "Data too large for a QR code, or its QR code did not decode back";
+
+/// Generates a QR code in SVG format, decoded back first if `verify` is set
+fn generate_qr_code(data: &str, size: u32, verify: bool) -> Result<String, String> {
+    let qr = encode_with_fallback(data, verify).ok_or_else(|| QR_FAILED.to_string())?;
+    Ok(render_qr_svg(&qr, size))
+}
+
+/// Renders `data` as a QR code made of Unicode half-blocks for display in a terminal.
+/// Light and dark are swapped so the code scans on the usual dark terminal background.
+pub fn render_qr_terminal(data: &str) -> Result<String, String> {
+    let qr = encode_with_fallback(data, true).ok_or_else(|| QR_FAILED.to_string())?;
+    Ok(qr.render::<qrcode::render::unicode::Dense1x2>()
+        .dark_color(qrcode::render::unicode::Dense1x2::Light)
+        .light_color(qrcode::render::unicode::Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Returns the module grid of a QR code for `data` as (width, row-major dark flags)
+pub fn qr_modules(data: &str) -> Result<(usize, Vec<bool>), String> {
+    let qr = encode_with_fallback(data, true).ok_or_else(|| QR_FAILED.to_string())?;
+    let modules = qr.to_colors().into_iter().map(|c| c == qrcode::Color::Dark).collect();
+    Ok((qr.width(), modules))
+}
+
+/// Decodes the first QR code found in a PNG or JPEG image, returning its text
+pub fn decode_qr_image(path: &Path) -> Result<String, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("Failed to open image {}: {}", path.display(), e))?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    let grids = prepared.detect_grids();
+    let grid = grids.first().ok_or_else(|| format!("No QR code found in {}", path.display()))?;
+    let (_, content) = grid.decode().map_err(|e| format!("Failed to decode QR code: {}", e))?;
+    Ok(content)
+}
+
+/// Renders a QR code as an SVG string
+fn render_qr_svg(qr: &QrCode, size: u32) -> String {
+    qr.render::<qrcode::render::svg::Color>()
         .min_dimensions(size, size)
         .quiet_zone(true)
         .dark_color(qrcode::render::svg::Color("#000000"))
         .light_color(qrcode::render::svg::Color("#ffffff"))
-        .build();
-    Ok(svg)
+        .build()
+}
+
+/// Encodes `data` at the highest error correction level (H, Q, M, then L) that fits and,
+/// if `verify` is set, decodes back to `data`
+fn encode_with_fallback(data: &str, verify: bool) -> Option<QrCode> {
+    [EcLevel::H, EcLevel::Q, EcLevel::M, EcLevel::L].into_iter().find_map(|level| {
+        let qr = QrCode::with_error_correction_level(data.as_bytes(), level).ok()?;
+        if verify && !qr_round_trips(&qr, data) {
+            log::warn!("QR code at error correction level {:?} did not decode back, trying a fallback", level);
+            return None;
+        }
+        Some(qr)
+    })
+}
+
+/// Rasterizes the modules of `qr` and decodes the image with an independent QR reader,
+/// checking that it yields exactly `data`. This catches encoding and capacity problems before
+/// a wallet is printed; the SVG drawn from the same modules is not decoded itself.
+fn qr_round_trips(qr: &QrCode, data: &str) -> bool {
+    let width = qr.width();
+    let colors = qr.to_colors();
+    let side = (width + 2 * VERIFY_QUIET_ZONE) as u32 * VERIFY_MODULE_PIXELS;
+    let image = image::GrayImage::from_fn(side, side, |x, y| {
+        let module = |pixel: u32| ((pixel / VERIFY_MODULE_PIXELS) as usize).checked_sub(VERIFY_QUIET_ZONE).filter(|&m| m < width);
+        let dark = module(x)
+            .zip(module(y))
+            .is_some_and(|(mx, my)| colors[my * width + mx] == qrcode::Color::Dark);
+        image::Luma([if dark { 0 } else { 255 }])
+    });
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    let grids = prepared.detect_grids();
+    grids.len() == 1 && grids[0].decode().is_ok_and(|(_, content)| content == data)
+}
+
+/// Splits `data` into up to `parts` pieces of about equal length, each prefixed with a
+/// `k/n:` header so the pieces can be told apart and put back in order after scanning
+fn split_qr_payload(data: &str, parts: usize) -> Vec<String> {
+    let chars: Vec<char> = data.chars().collect();
+    let chunks: Vec<String> = chars.chunks(chars.len().div_ceil(parts).max(1)).map(|c| c.iter().collect()).collect();
+    let total = chunks.len();
+    chunks.iter().enumerate().map(|(i, chunk)| format!("{}/{}:{}", i + 1, total, chunk)).collect()
+}
+
+/// Generates one or more SVG QR codes for `data`, degrading gracefully when the payload
+/// exceeds QR capacity, or its codes don't decode back with `verify`: first by lowering the
+/// error correction level, then by splitting the payload across several codes (see
+/// `split_qr_payload`).
+fn generate_qr_codes(data: &str, size: u32, verify: bool) -> Result<Vec<String>, String> {
+    if let Some(qr) = encode_with_fallback(data, verify) {
+        return Ok(vec![render_qr_svg(&qr, size)]);
+    }
+
+    for parts in 2..=MAX_QR_PARTS {
+        let codes: Option<Vec<QrCode>> = split_qr_payload(data, parts)
+            .iter()
+            .map(|part| encode_with_fallback(part, verify))
+            .collect();
+        if let Some(codes) = codes {
+            return Ok(codes.iter().map(|qr| render_qr_svg(qr, size)).collect());
+        }
+    }
+
+    Err(format!("Data too large to fit in {} QR codes that decode back", MAX_QR_PARTS))
+}
+
+/// Generates the HTML for a possibly split seed QR payload, labelling each part when split.
+/// Seed codes are always decoded back, since an unreadable one loses the wallet.
+fn generate_split_qr_html(data: &str, size: u32) -> Result<String, String> {
+    let codes = generate_qr_codes(data, size, true)?;
+    if codes.len() == 1 {
+        return Ok(codes.into_iter().next().unwrap_or_default());
+    }
+
+    let total = codes.len();
+    let parts: Vec<String> = codes
+        .into_iter()
+        .enumerate()
+        .map(|(i, svg)| {
+            format!(
+                r#"<div>{}<div class="qr-label">Part {} of {} (starts with "{}/{}:")</div></div>"#,
+                svg, i + 1, total, i + 1, total
+            )
+        })
+        .collect();
+    Ok(parts.join("\n"))
+}
+
+/// Version tag of the encrypted seed payload, `v2:<iterations>:<salt>:<nonce>:<ciphertext>`
+/// (hex fields). Payloads without it are the legacy XOR format.
+const ENCRYPTION_VERSION: &str = "v2";
+/// PBKDF2-HMAC-SHA256 iterations deriving the AES-256-GCM key from the password
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Derives the AES-256 key for `password` and `salt`
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), salt, iterations, key.as_mut());
+    key
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok()).collect()
+}
+
+/// Encrypts a seed phrase with AES-256-GCM under a key derived from the password with a
+/// random salt, returning the versioned payload. Reads the password from the terminal unless
+/// one is given.
+fn encrypt_seed(seed: &str, password: Option<&str>) -> Result<String, String> {
+    let password = match password {
+        Some(p) => Zeroizing::new(p.to_string()),
+        None => {
+            println!("Enter encryption password for paper wallet (not stored):");
+            Zeroizing::new(rpassword::read_password().map_err(|e| e.to_string())?)
+        }
+    };
+    if password.is_empty() {
+        return Err("An empty password would leave the seed unencrypted".to_string());
+    }
+    encrypt_seed_with(seed, &password, KDF_ITERATIONS)
+}
+
+fn encrypt_seed_with(seed: &str, password: &str, iterations: u32) -> Result<String, String> {
+    use aes_gcm::aead::{Aead, KeyInit, Payload};
+    use rand::RngCore;
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let key = derive_key(password, &salt, iterations);
+    let header = format!("{}:{}:{}:{}", ENCRYPTION_VERSION, iterations, to_hex(&salt), to_hex(&nonce));
+    let ciphertext = aes_gcm::Aes256Gcm::new_from_slice(key.as_ref())
+        .map_err(|e| e.to_string())?
+        .encrypt(&aes_gcm::Nonce::from(nonce), Payload { msg: seed.as_bytes(), aad: header.as_bytes() })
+        .map_err(|_| "Failed to encrypt the seed".to_string())?;
+    Ok(format!("{}:{}", header, to_hex(&ciphertext)))
+}
+
+/// Decrypts a `v2` payload (without the `ENCRYPTED:` prefix)
+fn decrypt_seed_v2(data: &str, password: &str) -> Result<Zeroizing<String>, String> {
+    use aes_gcm::aead::{Aead, KeyInit, Payload};
+
+    let malformed = || "Malformed encrypted seed payload".to_string();
+    let fields: Vec<&str> = data.split(':').collect();
+    let [_, iterations, salt, nonce, ciphertext] = fields[..] else {
+        return Err(malformed());
+    };
+    let iterations: u32 = iterations.parse().ok().filter(|&n| n > 0).ok_or_else(malformed)?;
+    let salt = from_hex(salt).ok_or_else(malformed)?;
+    let nonce: [u8; NONCE_LEN] = from_hex(nonce).and_then(|n| n.try_into().ok()).ok_or_else(malformed)?;
+    let ciphertext = from_hex(ciphertext).ok_or_else(malformed)?;
+    // The header is authenticated along with the ciphertext
+    let header = fields[..4].join(":");
+
+    let key = derive_key(password, &salt, iterations);
+    let plaintext = aes_gcm::Aes256Gcm::new_from_slice(key.as_ref())
+        .map_err(|e| e.to_string())?
+        .decrypt(&aes_gcm::Nonce::from(nonce), Payload { msg: &ciphertext, aad: header.as_bytes() })
+        .map(Zeroizing::new)
+        .map_err(|_| "Wrong password or corrupted payload".to_string())?;
+    String::from_utf8(plaintext.to_vec())
+        .map(Zeroizing::new)
+        .map_err(|_| "Wrong password or corrupted payload".to_string())
 }
 
-/// Simple XOR-based encryption (for obfuscation only) for seed phrases
-fn encrypt_seed(seed: &str) -> Result<String, String> {
-    println!("Enter encryption password for paper wallet (not stored):");
-    let password = rpassword::read_password().map_err(|e| e.to_string())?;
+/// The encrypted data of a scanned seed QR payload: its first line, without the
+/// `ENCRYPTED:` prefix (the password hint follows on later lines)
+fn payload_data(payload: &str) -> &str {
+    let first_line = payload.lines().next().unwrap_or_default().trim();
+    first_line.strip_prefix("ENCRYPTED:").unwrap_or(first_line)
+}
+
+/// Whether `payload` predates the versioned format: the repeating-key XOR of earlier
+/// releases, which known BIP39 words break without the password
+fn is_legacy_payload(payload: &str) -> bool {
+    !payload_data(payload).starts_with(&format!("{}:", ENCRYPTION_VERSION))
+}
+
+/// Reverses `encrypt_seed`: `payload` is the scanned QR text, with or without the
+/// `ENCRYPTED:` prefix and password hint line. Legacy XOR payloads are still read.
+fn decrypt_seed(payload: &str, password: &str) -> Result<Zeroizing<String>, String> {
+    let data = payload_data(payload);
+    if !is_legacy_payload(payload) {
+        return decrypt_seed_v2(data, password);
+    }
+    // An empty password left legacy seeds unencrypted
     if password.is_empty() {
-        return Ok(seed.to_string());
+        return Ok(Zeroizing::new(data.to_string()));
     }
-    let mut encrypted = String::with_capacity(seed.len() * 2);
-    let password_bytes: Vec<u8> = password.bytes().collect();
-    for (i, byte) in seed.bytes().enumerate() {
-        let key_byte = password_bytes[i % password_bytes.len()];
-        let encrypted_byte = byte ^ key_byte;
-        encrypted.push_str(&format!("{:02x}", encrypted_byte));
+    let bytes = from_hex(data).ok_or_else(|| "Payload is not an encrypted seed".to_string())?;
+    let password_bytes = password.as_bytes();
+    let decrypted: Zeroizing<Vec<u8>> = Zeroizing::new(
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ password_bytes[i % password_bytes.len()])
+            .collect(),
+    );
+    String::from_utf8(decrypted.to_vec())
+        .map(Zeroizing::new)
+        .map_err(|_| "Wrong password or corrupted payload".to_string())
+}
+
+/// Prompts for an encrypted paper wallet payload and its password, then prints the
+/// recovered seed and its first address so it can be compared with the wallet.
+pub fn decrypt_wallet_interactive() -> Result<(), String> {
+    println!("Paste the scanned seed QR payload (ENCRYPTED:...) and press Enter:");
+    let mut payload = Zeroizing::new(String::new());
+    std::io::stdin().read_line(&mut payload).map_err(|e| e.to_string())?;
+    println!("Enter the paper wallet password:");
+    let password = Zeroizing::new(rpassword::read_password().map_err(|e| e.to_string())?);
+
+    let seed = decrypt_seed(&payload, &password)?;
+    verify_mnemonic(&seed).map_err(|e| format!("Wrong password or corrupted payload ({})", e))?;
+    if is_legacy_payload(&payload) {
+        eprintln!("WARNING: this wallet uses the legacy XOR encryption of earlier releases, which can be");
+        eprintln!("WARNING: broken without the password. Move the funds to a new wallet.");
+    }
+
+    eprintln!("WARNING: the seed phrase is printed below. Clear your terminal scrollback afterwards");
+    eprintln!("and do not paste the payload as a command-line argument, where shell history keeps it.");
+    println!("Seed phrase ({}-word): {}", seed.split_whitespace().count(), seed.as_str());
+    if let Some(first) = generate_addresses(&seed, 1, AddressFormat::default(), ERGO_COIN_TYPE).first() {
+        println!("First address: {}", first.address);
+        println!("Check that this matches the address printed on the paper wallet.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    // Few iterations keep the tests fast; the count travels in the payload
+    const TEST_ITERATIONS: u32 = 1_000;
+
+    #[test]
+    fn encrypted_seed_round_trips() {
+        let payload = encrypt_seed_with(SEED, "correct horse", TEST_ITERATIONS).unwrap();
+        assert!(payload.starts_with("v2:1000:"));
+        assert!(!payload.contains("abandon"));
+        let decrypted = decrypt_seed(&format!("ENCRYPTED:{}\nHint: horse", payload), "correct horse").unwrap();
+        assert_eq!(decrypted.as_str(), SEED);
+    }
+
+    #[test]
+    fn encryption_uses_a_fresh_salt_and_nonce() {
+        let first = encrypt_seed_with(SEED, "pw", TEST_ITERATIONS).unwrap();
+        let second = encrypt_seed_with(SEED, "pw", TEST_ITERATIONS).unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn wrong_password_or_tampering_is_rejected() {
+        let payload = encrypt_seed_with(SEED, "right", TEST_ITERATIONS).unwrap();
+        assert!(decrypt_seed(&payload, "wrong").is_err());
+
+        // Changing the authenticated header or the ciphertext fails authentication
+        let tampered_header = payload.replacen("v2:1000:", "v2:1001:", 1);
+        assert!(decrypt_seed(&tampered_header, "right").is_err());
+        let last = payload.chars().last().unwrap();
+        let flipped = format!("{}{}", &payload[..payload.len() - 1], if last == '0' { '1' } else { '0' });
+        assert!(decrypt_seed(&flipped, "right").is_err());
+    }
+
+    #[test]
+    fn legacy_xor_payloads_are_detected_and_still_read() {
+        let legacy: String = SEED.bytes().zip(b"key".iter().cycle()).map(|(b, k)| format!("{:02x}", b ^ k)).collect();
+        let payload = format!("ENCRYPTED:{}\nHint: three letters", legacy);
+        assert!(is_legacy_payload(&payload));
+        assert_eq!(decrypt_seed(&payload, "key").unwrap().as_str(), SEED);
+
+        let current = encrypt_seed_with(SEED, "key", TEST_ITERATIONS).unwrap();
+        assert!(!is_legacy_payload(&format!("ENCRYPTED:{}", current)));
+    }
+
+    #[test]
+    fn empty_password_is_refused() {
+        assert!(encrypt_seed(SEED, Some("")).is_err());
+    }
+
+    #[test]
+    fn split_payload_parts_carry_their_position() {
+        let parts = split_qr_payload("abcdefg", 3);
+        assert_eq!(parts, vec!["1/3:abc", "2/3:def", "3/3:g"]);
+        // Fewer pieces than asked for when the data is short
+        assert_eq!(split_qr_payload("ab", 3), vec!["1/2:a", "2/2:b"]);
+    }
+
+    #[test]
+    fn longest_encrypted_seed_fits_one_code_at_high_error_correction() {
+        // 24 of the longest (8-letter) BIP39 words make the longest possible seed phrase
+        let seed = vec!["absolute"; 24].join(" ");
+        let encrypted = encrypt_seed_with(&seed, "correct horse", TEST_ITERATIONS).unwrap();
+        let payload = format!("ENCRYPTED:{}\nHint: {}", encrypted, "a fairly long password hint");
+
+        let codes = generate_qr_codes(&payload, 120, true).unwrap();
+        assert_eq!(codes.len(), 1);
+        let qr = encode_with_fallback(&payload, true).unwrap();
+        assert_eq!(qr.error_correction_level(), EcLevel::H);
+    }
+
+    #[test]
+    fn oversized_payloads_are_split_across_codes() {
+        // More than the 2,953 bytes a single code holds at the lowest error correction level
+        let data: String = (0..5_000).map(|i| char::from(b'a' + (i % 26) as u8)).collect();
+        assert!(encode_with_fallback(&data, false).is_none());
+
+        let codes = generate_qr_codes(&data, 200, false).unwrap();
+        assert_eq!(codes.len(), 2);
+        assert!(codes.iter().all(|svg| svg.starts_with("<?xml") || svg.starts_with("<svg")));
+        let joined: String = split_qr_payload(&data, 2)
+            .iter()
+            .map(|part| part.split_once(':').unwrap().1)
+            .collect();
+        assert_eq!(joined, data);
     }
-    Ok(encrypted)
 }
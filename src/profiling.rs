@@ -0,0 +1,29 @@
+//! Flamegraph-friendly timing of the hot paths, enabled by the `profile` feature.
+//!
+//! Seed generation, address derivation and pattern matching carry `tracing` spans; this
+//! module records them in folded-stack format for `inferno-flamegraph` or `flamegraph.pl`.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::prelude::*;
+
+/// Starts recording spans to `path`. The file is complete once the returned guard is dropped.
+pub fn start(path: &Path) -> Option<FlushGuard<BufWriter<File>>> {
+    let (layer, guard) = match FlameLayer::with_file(path) {
+        Ok(flame) => flame,
+        Err(e) => {
+            log::warn!("Could not create profile output {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    // Merge the worker threads into one stack; source locations would split identical frames
+    let layer = layer.with_threads_collapsed(true).with_file_and_line(false);
+    if let Err(e) = tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer)) {
+        log::warn!("Could not start profiling: {}", e);
+        return None;
+    }
+    println!("Profiling: writing hot-path timings to {}", path.display());
+    Some(guard)
+}
@@ -1,21 +1,71 @@
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use crate::utils::group_digits;
 
 /// Statistics summary: (total seeds, total addresses, seed rate, address rate, thread count)
 pub type StatsSummary = (usize, usize, f64, f64, usize);
 pub type ProgressCallback = Box<dyn Fn(usize, usize, f64, f64) + Send + Sync>;
 
+/// Instantaneous rates kept for median filtering; older ones are dropped
+pub(crate) const RATE_HISTORY_LEN: usize = 5;
+
+/// Rate limit for progress callbacks. Each callback owns its own, so callbacks on
+/// different processors never throttle each other.
+pub struct Throttle {
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, last: Mutex::new(None) }
+    }
+
+    /// Returns true (and restarts the interval) if `interval` has passed since the last time it did
+    pub fn ready(&self) -> bool {
+        let mut last = self.last.lock().unwrap();
+        if last.is_some_and(|at| at.elapsed() < self.interval) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+}
+
+/// Addresses checked relative to the number expected to find the results, e.g.
+/// "~42% of expected". Matches turn up at random, so this is a rough guide rather than a
+/// countdown, and a search can run well past 100%.
+pub fn expected_progress_label(addresses: usize, expected: f64) -> String {
+    let percent = addresses as f64 / expected * 100.0;
+    if percent > 100.0 {
+        ">100% of expected — tough luck so far".to_string()
+    } else if percent < 1.0 {
+        "<1% of expected".to_string()
+    } else {
+        format!("~{:.0}% of expected", percent)
+    }
+}
+
 /// Tracks progress, statistics, and calls progress update callbacks.
 pub struct ProgressTracker {
     pub total_seeds: Arc<AtomicUsize>,
     pub total_addresses: Arc<AtomicUsize>,
+    /// Matches found so far, shown on the progress bar without locking any results list
+    pub matches_found: Arc<AtomicUsize>,
     pub running: Arc<AtomicBool>,
     pub start_time: Instant,
     thread_count: usize,
     callback: Arc<Mutex<Option<ProgressCallback>>>,
     progress_bar: Option<Arc<ProgressBar>>,
+    // Addresses the estimator expects the search to need, shown as expected progress
+    expected_addresses: Arc<Mutex<Option<f64>>>,
+    // Last (seed rate, address rate) samples, at most RATE_HISTORY_LEN of them
+    rate_history: Arc<Mutex<VecDeque<(f64, f64)>>>,
+    // Sole owner of the monitoring thread's handle; joined by `stop`
+    monitor_handle: Mutex<Option<std::thread::JoinHandle<()>>>,
     smoothing_factor: f64,
     update_interval_secs: f64,
 }
@@ -38,11 +88,15 @@ impl ProgressTracker {
         Self {
             total_seeds: Arc::new(AtomicUsize::new(0)),
             total_addresses: Arc::new(AtomicUsize::new(0)),
+            matches_found: Arc::new(AtomicUsize::new(0)),
             running: Arc::new(AtomicBool::new(true)),
             start_time: Instant::now(),
             thread_count,
             callback: Arc::new(Mutex::new(None)),
             progress_bar,
+            expected_addresses: Arc::new(Mutex::new(None)),
+            rate_history: Arc::new(Mutex::new(VecDeque::with_capacity(RATE_HISTORY_LEN + 1))),
+            monitor_handle: Mutex::new(None),
             smoothing_factor: 0.2,     // EMA smoothing (20%)
             update_interval_secs: 0.5, // Update every 0.5 seconds
         }
@@ -56,6 +110,12 @@ impl ProgressTracker {
         *self.callback.lock().unwrap() = Some(Box::new(callback));
     }
 
+    /// Sets the number of addresses the search is expected to need, to show progress against it
+    /// in the progress bar. `None` (or an unusable estimate) shows no expected progress.
+    pub fn set_expected_addresses(&self, expected: Option<f64>) {
+        *self.expected_addresses.lock().unwrap() = expected.filter(|e| e.is_finite() && *e > 0.0);
+    }
+
     /// Records that `seeds` seeds and `addresses` addresses have been processed.
     pub fn record_processed(&self, seeds: usize, addresses: usize) {
         self.total_seeds.fetch_add(seeds, Ordering::Relaxed);
@@ -63,26 +123,30 @@ impl ProgressTracker {
     }
 
     /// Starts a thread that monitors progress and periodically updates the progress bar and callback.
-    pub fn start_monitoring_thread(&self) -> std::thread::JoinHandle<()> {
+    /// Any previous monitoring thread is stopped and joined first; the new thread is joined by `stop`.
+    pub fn start_monitoring_thread(&self) {
+        self.stop();
         self.running.store(true, Ordering::SeqCst);
 
         let total_seeds = Arc::clone(&self.total_seeds);
         let total_addresses = Arc::clone(&self.total_addresses);
+        let matches_found = Arc::clone(&self.matches_found);
         let running = Arc::clone(&self.running);
         let callback = Arc::clone(&self.callback);
         let progress_bar = self.progress_bar.clone();
+        let expected_addresses = Arc::clone(&self.expected_addresses);
+        let rate_history = Arc::clone(&self.rate_history);
+        rate_history.lock().unwrap().clear();
         let smoothing_factor = self.smoothing_factor;
         let update_interval = self.update_interval_secs;
 
-        std::thread::spawn(move || {
+        let handle = std::thread::spawn(move || {
             let mut last_seeds = 0;
             let mut last_addresses = 0;
             let mut last_time = Instant::now();
             let mut first_update = true;
             let mut smoothed_seed_rate = 0.0;
             let mut smoothed_addr_rate = 0.0;
-            let mut seed_rate_history: Vec<f64> = Vec::with_capacity(5);
-            let mut addr_rate_history: Vec<f64> = Vec::with_capacity(5);
 
             while running.load(Ordering::Relaxed) {
                 let current_seeds = total_seeds.load(Ordering::Relaxed);
@@ -99,16 +163,15 @@ impl ProgressTracker {
                     let instant_addr_rate = delta_addresses as f64 / delta_time;
 
                     // Update history (for median filtering).
-                    seed_rate_history.push(instant_seed_rate);
-                    addr_rate_history.push(instant_addr_rate);
-                    if seed_rate_history.len() > 5 {
-                        seed_rate_history.remove(0);
-                        addr_rate_history.remove(0);
-                    }
-
-                    let mut seed_rates_sorted = seed_rate_history.clone();
+                    let (mut seed_rates_sorted, mut addr_rates_sorted): (Vec<f64>, Vec<f64>) = {
+                        let mut history = rate_history.lock().unwrap();
+                        history.push_back((instant_seed_rate, instant_addr_rate));
+                        if history.len() > RATE_HISTORY_LEN {
+                            history.pop_front();
+                        }
+                        history.iter().copied().unzip()
+                    };
                     seed_rates_sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-                    let mut addr_rates_sorted = addr_rate_history.clone();
                     addr_rates_sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
                     let filtered_seed_rate = if seed_rates_sorted.len() >= 3 {
@@ -134,9 +197,23 @@ impl ProgressTracker {
                     }
 
                     if let Some(pb) = &progress_bar {
+                        let expected = expected_addresses
+                            .lock()
+                            .unwrap()
+                            .map(|expected| format!(" [{}]", expected_progress_label(current_addresses, expected)))
+                            .unwrap_or_default();
+                        let found = match matches_found.load(Ordering::Relaxed) {
+                            0 => String::new(),
+                            n => format!(" {} found.", group_digits(n as u64)),
+                        };
                         pb.set_message(format!(
-                            "Checked {} seeds ({:.0} seeds/s) and {} addresses ({:.0} addr/s)...",
-                            current_seeds, smoothed_seed_rate, current_addresses, smoothed_addr_rate
+                            "Checked {} seeds ({} seeds/s) and {} addresses ({} addr/s)...{}{}",
+                            group_digits(current_seeds as u64),
+                            group_digits(smoothed_seed_rate.round() as u64),
+                            group_digits(current_addresses as u64),
+                            group_digits(smoothed_addr_rate.round() as u64),
+                            found,
+                            expected
                         ));
                     }
 
@@ -155,12 +232,37 @@ impl ProgressTracker {
             if let Some(pb) = &progress_bar {
                 pb.finish_and_clear();
             }
-        })
+        });
+        *self.monitor_handle.lock().unwrap() = Some(handle);
+    }
+
+    /// Shows or hides the console progress bar, e.g. while another front end owns the terminal
+    pub fn set_progress_bar_visible(&self, visible: bool) {
+        if let Some(pb) = &self.progress_bar {
+            pb.set_draw_target(if visible { ProgressDrawTarget::stderr() } else { ProgressDrawTarget::hidden() });
+        }
     }
 
-    /// Stops the progress monitoring.
+    /// Stops the progress monitoring and waits for the monitoring thread to exit.
+    /// Safe to call from any path (search end, cancel, reset) and more than once.
     pub fn stop(&self) {
-        self.running.store(false, Ordering::Relaxed);
+        self.running.store(false, Ordering::SeqCst);
+        let handle = self.monitor_handle.lock().unwrap().take();
+        if let Some(handle) = handle {
+            let _ = handle.join();
+        }
+    }
+
+    /// Number of rate samples currently kept for median filtering
+    #[cfg(test)]
+    pub(crate) fn rate_history_len(&self) -> usize {
+        self.rate_history.lock().unwrap().len()
+    }
+
+    /// Whether a monitoring thread is running, i.e. not yet joined by `stop`
+    #[cfg(test)]
+    pub(crate) fn is_monitoring(&self) -> bool {
+        self.monitor_handle.lock().unwrap().is_some()
     }
 
     /// Returns final statistics as a tuple:
@@ -174,11 +276,11 @@ impl ProgressTracker {
         (total_seeds, total_addresses, seed_rate, address_rate, self.thread_count)
     }
 
-    /// Resets the tracker to its initial state.
+    /// Resets the tracker to its initial state, stopping and joining any monitoring thread.
     /// Note: Running is not set to true here to avoid race conditions;
     /// it will be re-enabled when `start_monitoring_thread` is called.
     pub fn reset(&self) {
-        self.running.store(false, Ordering::SeqCst);
+        self.stop();
         self.total_seeds.store(0, Ordering::Relaxed);
         self.total_addresses.store(0, Ordering::Relaxed);
         if let Some(pb) = &self.progress_bar {
@@ -186,3 +288,21 @@ impl ProgressTracker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_are_independent_and_reopen_after_the_interval() {
+        let first = Throttle::new(Duration::from_millis(100));
+        let second = Throttle::new(Duration::from_millis(100));
+        assert!(first.ready());
+        assert!(!first.ready());
+        // Another callback's throttle is unaffected
+        assert!(second.ready());
+        std::thread::sleep(Duration::from_millis(120));
+        assert!(first.ready());
+        assert!(!first.ready());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::utils;
+use std::path::PathBuf;
+
+/// File (in the home directory) where the last measured address rate is stored
+const RATE_CACHE_FILE: &str = ".ergo-vanitygen-rate.json";
+
+/// Searches shorter than this don't reach a steady rate and aren't cached
+pub const MIN_MEASURE_SECONDS: f64 = 5.0;
+
+/// Location of the rate cache, if a home directory is known
+pub fn cache_path() -> Option<PathBuf> {
+    utils::home_file(RATE_CACHE_FILE)
+}
+
+/// Stores the addresses/second measured on this machine with these search settings
+/// for later `--estimate` runs
+pub fn save_rate(addresses_per_second: f64, word_count: usize, addresses_per_seed: u32) {
+    if !addresses_per_second.is_finite() || addresses_per_second <= 0.0 {
+        return;
+    }
+    let Some(path) = cache_path() else {
+        return;
+    };
+    let data = serde_json::json!({
+        "addresses_per_second": addresses_per_second,
+        "word_count": word_count,
+        "addresses_per_seed": addresses_per_seed,
+    });
+    match std::fs::write(&path, data.to_string()) {
+        Ok(()) => log::info!("Saved measured rate of {:.0} addresses/s to {}", addresses_per_second, path.display()),
+        Err(e) => log::warn!("Could not save measured rate to {}: {}", path.display(), e),
+    }
+}
+
+/// Loads the last measured addresses/second if it was measured with the same word count
+/// and addresses per seed
+pub fn load_rate(word_count: usize, addresses_per_seed: u32) -> Option<f64> {
+    let data = std::fs::read_to_string(cache_path()?).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&data).ok()?;
+    if value["word_count"].as_u64()? as usize != word_count
+        || value["addresses_per_seed"].as_u64()? as u32 != addresses_per_seed
+    {
+        return None;
+    }
+    value["addresses_per_second"].as_f64().filter(|r| r.is_finite() && *r > 0.0)
+}
+
+/// Deletes the cached rate so estimates fall back to the built-in speeds
+pub fn reset() -> std::io::Result<()> {
+    match cache_path() {
+        Some(path) if path.exists() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
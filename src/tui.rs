@@ -0,0 +1,228 @@
+//! Terminal UI for headless machines and SSH sessions: live stats, found matches and a log.
+//!
+//! Mirrors the GUI's data flow: the processor's result and progress callbacks fill shared
+//! state while the search runs on its own thread, and the screen is redrawn from that state.
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use crate::address_processor::{AddressProcessor, MatchResult};
+use crate::matcher::PatternMatcher;
+use crate::progress::StatsSummary;
+use crate::utils::group_digits;
+
+/// Maximum number of log lines kept
+const MAX_LOG_LINES: usize = 200;
+
+/// How long to wait for a key press between redraws
+const TICK: Duration = Duration::from_millis(200);
+
+/// Restores the terminal when dropped, including on errors
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+/// Screen state, filled by the processor callbacks and the event loop
+struct TuiState {
+    results: Arc<Mutex<Vec<MatchResult>>>,
+    stats: Arc<Mutex<Option<StatsSummary>>>,
+    logs: VecDeque<String>,
+    logged_results: usize,
+    num_results: usize,
+    start_time: Instant,
+    finished_at: Option<Instant>,
+    cancelling: bool,
+}
+
+impl TuiState {
+    fn log(&mut self, message: impl Into<String>) {
+        self.logs.push_back(message.into());
+        while self.logs.len() > MAX_LOG_LINES {
+            self.logs.pop_front();
+        }
+    }
+
+    /// Logs new matches and the end of the search
+    fn update(&mut self, search_done: bool) {
+        let new: Vec<String> = self.results.lock().unwrap()[self.logged_results..]
+            .iter()
+            .map(|(_, address, pattern, position, _, _)| format!("Match: {} (pattern {}, index {})", address, pattern, position))
+            .collect();
+        self.logged_results += new.len();
+        for line in new {
+            self.log(line);
+        }
+        if search_done && self.finished_at.is_none() {
+            self.finished_at = Some(Instant::now());
+            self.log(if self.cancelling { "Search cancelled" } else { "Search finished" });
+            self.log("Press q to exit and print the results");
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match (self.finished_at.is_some(), self.cancelling) {
+            (true, true) => "Cancelled",
+            (true, false) => "Finished",
+            (false, true) => "Cancelling...",
+            (false, false) => "Searching",
+        }
+    }
+}
+
+/// Runs the search in a terminal UI until the user quits, returning every match found.
+/// Seeds are not shown on screen; the caller prints them after the terminal is restored.
+/// `q`/Esc quits (cancelling a running search), `c` cancels the search but keeps the screen open.
+pub fn run(
+    processor: Arc<AddressProcessor>,
+    matcher: PatternMatcher,
+    word_count: usize,
+    num_results: usize,
+    balanced: bool,
+    addresses_per_seed: u32,
+) -> io::Result<Vec<MatchResult>> {
+    let mut state = TuiState {
+        results: Arc::new(Mutex::new(Vec::new())),
+        stats: Arc::new(Mutex::new(None)),
+        logs: VecDeque::new(),
+        logged_results: 0,
+        num_results,
+        start_time: Instant::now(),
+        finished_at: None,
+        cancelling: false,
+    };
+
+    // Same callbacks as the GUI: collect matches and the latest stats
+    let results_for_callback = Arc::clone(&state.results);
+    processor.set_result_callback(move |mnemonic, address, pattern, position, word_count, checked| {
+        results_for_callback.lock().unwrap().push((
+            mnemonic.to_string(),
+            address.to_string(),
+            pattern.to_string(),
+            position,
+            word_count,
+            checked,
+        ));
+    });
+    let thread_count = processor.get_stats().4;
+    let stats_for_callback = Arc::clone(&state.stats);
+    processor.set_detailed_progress_callback(move |seeds, addresses, seed_rate, addr_rate, _found, _tally| {
+        *stats_for_callback.lock().unwrap() = Some((seeds, addresses, seed_rate, addr_rate, thread_count));
+    });
+    processor.set_quiet(true);
+
+    enable_raw_mode()?;
+    let guard = TerminalGuard;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let search = Arc::clone(&processor);
+    let handle = std::thread::Builder::new()
+        .name("address_search".to_string())
+        .spawn(move || search.find_matches(matcher, word_count, num_results, balanced, addresses_per_seed))?;
+    state.log(format!(
+        "Searching for {} matches, {} addresses per seed",
+        num_results, addresses_per_seed
+    ));
+
+    loop {
+        state.update(handle.is_finished());
+        terminal.draw(|frame| draw(frame, &state))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            _ if ctrl_c => break,
+            KeyCode::Char('c') if !state.cancelling && state.finished_at.is_none() => {
+                state.cancelling = true;
+                state.log("Cancelling search...");
+                processor.cancel();
+            }
+            _ => {}
+        }
+    }
+
+    if !handle.is_finished() {
+        processor.cancel();
+    }
+    drop(terminal);
+    drop(guard);
+    processor.set_quiet(false);
+    handle.join().map_err(|_| io::Error::other("the search thread panicked"))?;
+    // Every streamed match, including those past the target with --keep-going
+    let results = state.results.lock().unwrap().clone();
+    Ok(results)
+}
+
+/// Draws stats, matches, log and key help from top to bottom
+fn draw(frame: &mut Frame, state: &TuiState) {
+    let [stats_area, matches_area, log_area, help_area] = Layout::vertical([
+        Constraint::Length(8),
+        Constraint::Min(5),
+        Constraint::Length(10),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let elapsed = state.finished_at.unwrap_or_else(Instant::now).duration_since(state.start_time);
+    let (seeds, addresses, seed_rate, addr_rate, threads) = state.stats.lock().unwrap().unwrap_or_default();
+    let found = state.results.lock().unwrap().len();
+    let stats = vec![
+        Line::from(format!("Status:    {}", state.status())),
+        Line::from(format!("Elapsed:   {:.0}s", elapsed.as_secs_f64())),
+        Line::from(format!("Checked:   {} seeds, {} addresses", group_digits(seeds as u64), group_digits(addresses as u64))),
+        Line::from(format!(
+            "Speed:     {} seeds/s, {} addresses/s",
+            group_digits(seed_rate.round() as u64),
+            group_digits(addr_rate.round() as u64)
+        )),
+        Line::from(format!("Threads:   {}", threads)),
+        Line::from(format!("Found:     {} (target {})", found, state.num_results)),
+    ];
+    frame.render_widget(Paragraph::new(stats).block(Block::default().borders(Borders::ALL).title("Statistics")), stats_area);
+
+    let matches: Vec<ListItem> = state.results.lock().unwrap()
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, (_, address, pattern, position, _, _))| {
+            ListItem::new(format!("#{:<4} {}  {}  index {}", i + 1, address, pattern, position))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(matches).block(Block::default().borders(Borders::ALL).title("Matches (seed phrases are printed when you quit)")),
+        matches_area,
+    );
+
+    let visible = log_area.height.saturating_sub(2) as usize;
+    let logs: Vec<ListItem> = state.logs
+        .iter()
+        .skip(state.logs.len().saturating_sub(visible))
+        .map(|line| ListItem::new(line.as_str()))
+        .collect();
+    frame.render_widget(List::new(logs).block(Block::default().borders(Borders::ALL).title("Log")), log_area);
+
+    frame.render_widget(
+        Paragraph::new(" q quit   c cancel search").style(Style::default().fg(Color::DarkGray)),
+        help_area,
+    );
+}
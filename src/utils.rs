@@ -1,16 +1,53 @@
 use ergo_lib::{
     ergotree_ir::{
-        chain::address::{Address, NetworkPrefix, AddressEncoder},
+        chain::address::{Address, AddressTypePrefix, NetworkPrefix, AddressEncoder},
     },
     wallet::{
-        derivation_path::{ChildIndexHardened, ChildIndexNormal, DerivationPath},
+        derivation_path::{ChildIndex, DerivationPath},
         ext_secret_key::ExtSecretKey,
         mnemonic::Mnemonic,
         mnemonic_generator::{Language, MnemonicGenerator},
     },
 };
-use rand::Rng;
+use rand::rngs::OsRng;
+use rand::{Rng, RngCore};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
 use std::ops::{Deref, Drop};
+use zeroize::Zeroizing;
+
+/// Text encoding applied to derived addresses.
+///
+/// This is the extension point for new address encodings: add a variant, its arm in
+/// `encode` and its `charset`. Address generation, the processor and the matcher's pattern
+/// validation all go through this type, so nothing else needs to change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressFormat {
+    /// Base58Check with the mainnet prefix, as used by Ergo today
+    #[default]
+    Base58,
+}
+
+impl AddressFormat {
+    /// Encodes a derived address as text
+    pub fn encode(&self, address: &Address) -> String {
+        match self {
+            AddressFormat::Base58 => AddressEncoder::encode_address_as_string(NetworkPrefix::Mainnet, address),
+        }
+    }
+
+    /// Characters that can appear in an encoded address
+    pub fn charset(&self) -> &'static str {
+        match self {
+            AddressFormat::Base58 => "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+        }
+    }
+
+    /// Whether `c` can appear in an encoded address
+    pub fn is_valid_char(&self, c: char) -> bool {
+        self.charset().contains(c)
+    }
+}
 
 /// Represents an address along with its derivation position.
 #[derive(Debug)]
@@ -61,31 +98,213 @@ impl Drop for SecureSeed {
     }
 }
 
+lazy_static::lazy_static! {
+    // Measured once by encoding a sample address, so estimates use the real length
+    static ref P2PK_ADDRESS_LENGTH: usize = {
+        let (seed, _) = generate_secure_mnemonic(12, EntropySource::Csprng);
+        generate_addresses(seed.as_str(), 1, AddressFormat::default(), ERGO_COIN_TYPE)
+            .first()
+            .map(|info| info.address.len())
+            .expect("Failed to encode sample address")
+    };
+}
+
+/// Returns the length of an encoded mainnet P2PK address (51 characters).
+pub fn p2pk_address_length() -> usize {
+    *P2PK_ADDRESS_LENGTH
+}
+
+/// Characters a start pattern can begin with: those found as the second character of Base58
+/// addresses of `address_type` on `network`. The first character and this range are fixed by
+/// the leading network/type byte (and, for P2PK, the compressed key's 0x02/0x03 byte). None
+/// when every character occurs there, as for script addresses, whose first character varies.
+pub fn valid_start_chars(network: NetworkPrefix, address_type: AddressTypePrefix) -> Option<&'static [char]> {
+    match (network, address_type) {
+        (NetworkPrefix::Mainnet, AddressTypePrefix::P2Pk) => Some(&['e', 'f', 'g', 'h', 'i']),
+        (NetworkPrefix::Testnet, AddressTypePrefix::P2Pk) => Some(&['W']),
+        (_, AddressTypePrefix::Pay2Sh | AddressTypePrefix::Pay2S) => None,
+    }
+}
+
+/// Start characters of the mainnet P2PK addresses this tool generates (`9e...` to `9i...`)
+pub fn p2pk_start_chars() -> &'static [char] {
+    valid_start_chars(NetworkPrefix::Mainnet, AddressTypePrefix::P2Pk).expect("P2PK addresses have fixed start characters")
+}
+
+/// Whether a start pattern may begin with `c` in a generated address; without `case_sensitive`,
+/// either case counts
+pub fn is_p2pk_start_char(c: char, case_sensitive: bool) -> bool {
+    p2pk_start_chars()
+        .iter()
+        .any(|&valid| if case_sensitive { valid == c } else { valid.eq_ignore_ascii_case(&c) })
+}
+
+/// Lists characters for messages, e.g. "e, f, g, h, or i"
+pub fn describe_chars(chars: &[char]) -> String {
+    match chars {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [init @ .., last] => {
+            let init: Vec<String> = init.iter().map(char::to_string).collect();
+            format!("{}{} or {}", init.join(", "), if init.len() > 1 { "," } else { "" }, last)
+        }
+    }
+}
+
+/// How found seeds are printed: as the BIP39 word list, as raw entropy hex, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SeedOutputFormat {
+    Words,
+    Hex,
+    Both,
+}
+
+impl SeedOutputFormat {
+    pub fn shows_words(&self) -> bool {
+        matches!(self, SeedOutputFormat::Words | SeedOutputFormat::Both)
+    }
+
+    pub fn shows_hex(&self) -> bool {
+        matches!(self, SeedOutputFormat::Hex | SeedOutputFormat::Both)
+    }
+}
+
+/// Random source for seed entropy.
+///
+/// Both are cryptographically secure. `Csprng` is `rand`'s thread-local ChaCha12 generator,
+/// seeded and periodically reseeded from the OS; it is fast and what the search uses by default.
+/// `Os` asks the operating system (`getrandom`) directly for every seed, for users whose
+/// randomness requirements call for OS entropy with no userspace state in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum EntropySource {
+    Os,
+    Csprng,
+}
+
+/// Recovers the BIP39 entropy from a mnemonic and returns it as lowercase hex.
+///
+/// Both the raw entropy and the hex string are held in zeroizing buffers.
+pub fn seed_entropy_hex(mnemonic: &str) -> Result<Zeroizing<String>, String> {
+    let parsed = bip39::Mnemonic::parse_in_normalized(bip39::Language::English, mnemonic)
+        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+    let entropy = Zeroizing::new(parsed.to_entropy());
+
+    let mut hex = Zeroizing::new(String::with_capacity(entropy.len() * 2));
+    for byte in entropy.iter() {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    Ok(hex)
+}
+
+/// Checks that a mnemonic is a well-formed English BIP39 phrase with a valid checksum.
+pub fn verify_mnemonic(mnemonic: &str) -> Result<(), String> {
+    bip39::Mnemonic::parse_in_normalized(bip39::Language::English, mnemonic)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Formats a count compactly for display, e.g. 950, 12.5K, 1.2M, 3.4B.
+pub fn format_count(count: usize) -> String {
+    let n = count as f64;
+    if n < 1_000.0 {
+        count.to_string()
+    } else if n < 1_000_000.0 {
+        format!("{:.1}K", n / 1_000.0)
+    } else if n < 1_000_000_000.0 {
+        format!("{:.1}M", n / 1_000_000.0)
+    } else {
+        format!("{:.1}B", n / 1_000_000_000.0)
+    }
+}
+
+/// Formats a number with comma thousands separators, e.g. 123,456,789.
+pub fn group_digits(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// BIP44 coin type registered for Ergo, the `429'` in m/44'/429'/0'/0/idx. Standard Ergo
+/// wallets only derive addresses under this coin type.
+pub const ERGO_COIN_TYPE: u32 = 429;
+
+/// Derivation path m/44'/`coin_type`'/`account`'/0/`index` (change is always 0, per EIP-3)
+pub fn derivation_path(coin_type: u32, account: u32, index: u32) -> Result<DerivationPath, String> {
+    let indices = [
+        ChildIndex::hardened(44),
+        ChildIndex::hardened(coin_type),
+        ChildIndex::hardened(account),
+        ChildIndex::normal(0),
+        ChildIndex::normal(index),
+    ];
+    let indices: Vec<ChildIndex> = indices
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid derivation path {}: {}", derivation_path_string(coin_type, account, index), e))?;
+    Ok(DerivationPath::from(indices.into_boxed_slice()))
+}
+
+/// `derivation_path` as shown to the user
+pub fn derivation_path_string(coin_type: u32, account: u32, index: u32) -> String {
+    format!("m/44'/{}'/{}'/0/{}", coin_type, account, index)
+}
+
 /// Generates a list of addresses from a given mnemonic.
 /// 
 /// The function derives a master key from the mnemonic, then generates `count` addresses
-/// using the derivation path m/44'/429'/0'/0/idx. It returns a vector of `AddressInfo`.
-pub fn generate_addresses(mnemonic: &str, count: u32) -> Vec<AddressInfo> {
+/// using the derivation path m/44'/`coin_type`'/0'/0/idx (`ERGO_COIN_TYPE` for Ergo wallets),
+/// encoded with `format`. It returns a vector of `AddressInfo`.
+#[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
+pub fn generate_addresses(mnemonic: &str, count: u32, format: AddressFormat, coin_type: u32) -> Vec<AddressInfo> {
+    derive_addresses(mnemonic, count, format, coin_type).collect()
+}
+
+/// Derives the same addresses as `generate_addresses`, but lazily: each address is derived
+/// when the iterator reaches it, so a caller that stops early skips the rest.
+#[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
+pub fn derive_addresses(mnemonic: &str, count: u32, format: AddressFormat, coin_type: u32) -> impl Iterator<Item = AddressInfo> {
     // Create the seed from the mnemonic with an empty password.
-    let seed = Mnemonic::to_seed(mnemonic, "");
+    let seed = Zeroizing::new(Mnemonic::to_seed(mnemonic, ""));
+    derive_addresses_from_seed(&*seed, count, format, coin_type).expect("Failed to derive addresses")
+}
+
+/// Generates addresses like `generate_addresses`, but from the 64-byte BIP39 seed
+/// (the PBKDF2 output of a mnemonic) instead of the mnemonic itself.
+///
+/// Advanced and security-sensitive: whoever knows these bytes controls the addresses, so
+/// they need the same care as the mnemonic they came from.
+#[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
+pub fn generate_addresses_from_seed(seed_bytes: &[u8], count: u32, format: AddressFormat, coin_type: u32) -> Result<Vec<AddressInfo>, String> {
+    derive_addresses_from_seed(seed_bytes, count, format, coin_type).map(Iterator::collect)
+}
+
+/// Lazily derives addresses 0..count of a 64-byte BIP39 seed (see `derive_addresses`)
+fn derive_addresses_from_seed(seed_bytes: &[u8], count: u32, format: AddressFormat, coin_type: u32) -> Result<impl Iterator<Item = AddressInfo>, String> {
+    let seed: [u8; 64] = seed_bytes
+        .try_into()
+        .map_err(|_| format!("Seed must be 64 bytes, got {}", seed_bytes.len()))?;
+    let seed = Zeroizing::new(seed);
 
     // Derive the master key.
-    let master_key = ExtSecretKey::derive_master(seed)
-        .expect("Failed to derive master key");
+    let master_key = ExtSecretKey::derive_master(*seed)
+        .map_err(|e| format!("Failed to derive master key: {}", e))?;
 
-    // Use account index 0 (hardened).
-    let account = ChildIndexHardened::from_31_bit(0)
-        .expect("Invalid account index");
+    // Generate addresses for indices 0 to count - 1 of account 0.
+    Ok((0..count)
+        .map(move |idx| {
+            // The iterator is consumed outside the `derive_addresses` span, so time each address on its own
+            #[cfg(feature = "profile")]
+            let _span = tracing::trace_span!("derive_address").entered();
 
-    // Generate addresses for indices 0 to count - 1.
-    (0..count)
-        .map(|idx| {
-            // Build derivation path: m/44'/429'/0'/0/idx
-            let path = DerivationPath::new(
-                account,
-                vec![ChildIndexNormal::normal(idx)
-                    .expect("Invalid address index")],
-            );
+            // Build derivation path: m/44'/coin_type'/0'/0/idx
+            let path = derivation_path(coin_type, 0, idx)
+                .expect("Invalid derivation path");
 
             // Derive the key for the given path.
             let derived_key = master_key.derive(path)
@@ -96,43 +315,247 @@ pub fn generate_addresses(mnemonic: &str, count: u32) -> Vec<AddressInfo> {
                 .expect("Failed to get public key");
             let address: Address = ext_pub_key.into();
 
-            // Encode the address with Mainnet prefix.
-            let encoded_address = AddressEncoder::encode_address_as_string(NetworkPrefix::Mainnet, &address);
+            // Encode the address in the requested format.
+            let encoded_address = format.encode(&address);
 
             AddressInfo {
                 address: encoded_address,
                 position: idx,
             }
+        }))
+}
+
+/// Builds the English BIP39 mnemonic for caller-supplied entropy of 128, 160 or 256 bits
+/// (12, 15 or 24 words), bypassing the internal RNG.
+///
+/// Advanced and security-sensitive: the seed is only as strong as the entropy source, and
+/// entropy that is reused or predictable gives away the wallet.
+pub fn generate_mnemonic_from_entropy(entropy: &[u8]) -> Result<SecureSeed, String> {
+    if ![16, 20, 32].contains(&entropy.len()) {
+        return Err(format!(
+            "Entropy must be 128, 160 or 256 bits (16, 20 or 32 bytes), got {} bytes",
+            entropy.len()
+        ));
+    }
+    let mnemonic = bip39::Mnemonic::from_entropy_in(bip39::Language::English, entropy)
+        .map_err(|e| e.to_string())?;
+    let phrase = Zeroizing::new(mnemonic.to_string());
+    Ok(SecureSeed::new(&phrase))
+}
+
+/// Parses hex entropy as typed by the user (surrounding whitespace ignored).
+pub fn parse_hex_entropy(hex: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    let hex = hex.trim();
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("Entropy must be an even number of hex characters".to_string());
+    }
+    Ok(Zeroizing::new(
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or_default())
+            .collect(),
+    ))
+}
+
+/// Derives `count` mnemonics from `base_entropy`, starting at seed `first`: seed `n` (counting
+/// from 1) uses the base entropy with `n` XORed, big-endian, into its last 8 bytes. The same
+/// base and range always give the same seeds, which makes a search reproducible.
+///
+/// FOR TESTING ONLY: anyone who knows the base entropy knows every seed in the sequence.
+/// Never send real funds to these addresses.
+pub fn deterministic_mnemonics(base_entropy: &[u8], first: u64, count: usize) -> Result<Vec<SecureSeed>, String> {
+    let end = first
+        .checked_add(count as u64)
+        .ok_or_else(|| "The seed range runs past the end of the sequence".to_string())?;
+    (first..end)
+        .map(|n| {
+            let mut entropy = Zeroizing::new(base_entropy.to_vec());
+            let tail = entropy.len().saturating_sub(8);
+            for (byte, counter) in entropy[tail..].iter_mut().zip(n.to_be_bytes()) {
+                *byte ^= counter;
+            }
+            generate_mnemonic_from_entropy(&entropy)
         })
         .collect()
 }
 
+/// Path of `name` in the user's home directory, if one is known
+pub fn home_file(name: &str) -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(name))
+}
+
+/// Creates `dir` if needed and checks that files can be written there, so a bad
+/// `--output-dir` fails before a search rather than when its results are saved.
+pub fn prepare_output_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".ergo-vanitygen-write-test");
+    std::fs::write(&probe, b"").map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Placeholder seed attached to simulated results so they can't be mistaken for real wallets
+pub const SIMULATED_SEED: &str = "SIMULATED - NOT A REAL WALLET";
+
+/// Generates `count` random strings in the charset of `format`, shaped like mainnet P2PK addresses.
+///
+/// These are NOT real addresses: no key is derived. They exist so the matcher, progress
+/// reporting and UI can be exercised quickly without the cost of key derivation.
+pub fn generate_simulated_addresses(count: u32, format: AddressFormat) -> Vec<AddressInfo> {
+    let mut rng = rand::thread_rng();
+    let length = p2pk_address_length();
+    let charset = format.charset().as_bytes();
+    (0..count)
+        .map(|idx| {
+            let mut address = String::with_capacity(length);
+            address.push('9');
+            let start_chars = p2pk_start_chars();
+            address.push(start_chars[rng.gen_range(0..start_chars.len())]);
+            for _ in 2..length {
+                address.push(charset[rng.gen_range(0..charset.len())] as char);
+            }
+            AddressInfo {
+                address,
+                position: idx,
+            }
+        })
+        .collect()
+}
+
+/// Relative weights for 12, 15 and 24-word seeds when the word count is chosen at random
+pub type WordMix = [u32; 3];
+
+/// Equal odds for every supported length
+pub const UNIFORM_WORD_MIX: WordMix = [1, 1, 1];
+
+/// Largest weight accepted for one length, so the weights can always be summed
+pub const MAX_WORD_MIX_WEIGHT: u32 = 1000;
+
+/// Supported seed lengths, in `WordMix` order
+const WORD_COUNTS: [usize; 3] = [12, 15, 24];
+
+/// Picks 12, 15 or 24 at random in proportion to `mix` (uniformly if every weight is 0)
+pub fn random_word_count(mix: &WordMix) -> usize {
+    let total: u32 = mix.iter().sum();
+    if total == 0 {
+        return random_word_count(&UNIFORM_WORD_MIX);
+    }
+    let mut roll = rand::thread_rng().gen_range(0..total);
+    for (&count, &weight) in WORD_COUNTS.iter().zip(mix) {
+        if roll < weight {
+            return count;
+        }
+        roll -= weight;
+    }
+    unreachable!("roll is below the total weight")
+}
+
 /// Generates a mnemonic phrase and returns it wrapped in a SecureSeed along with its actual word count.
 /// 
 /// If `word_count` is 0, a supported length is chosen at random (12, 15, or 24 words).
-/// Otherwise, only 12, 15, or 24 are allowed.
-pub fn generate_secure_mnemonic(word_count: usize) -> (SecureSeed, usize) {
-    let (strength, actual_word_count) = if word_count == 0 {
-        let supported_lengths = [12, 15, 24];
-        let random_index = rand::thread_rng().gen_range(0..supported_lengths.len());
-        match supported_lengths[random_index] {
-            12 => (128, 12),
-            15 => (160, 15),
-            24 => (256, 24),
-            _ => unreachable!(),
-        }
-    } else {
-        match word_count {
-            12 => (128, 12),
-            15 => (160, 15),
-            24 => (256, 24),
-            _ => panic!("Unsupported word count"),
-        }
+/// Otherwise, only 12, 15, or 24 are allowed. The entropy is drawn from `source`.
+#[cfg_attr(feature = "profile", tracing::instrument(level = "trace", skip_all))]
+pub fn generate_secure_mnemonic(word_count: usize, source: EntropySource) -> (SecureSeed, usize) {
+    let word_count = if word_count == 0 { random_word_count(&UNIFORM_WORD_MIX) } else { word_count };
+    let (strength, actual_word_count) = match word_count {
+        12 => (128, 12),
+        15 => (160, 15),
+        24 => (256, 24),
+        _ => panic!("Unsupported word count"),
     };
 
     let generator = MnemonicGenerator::new(Language::English, strength);
-    let mnemonic = generator.generate()
-        .expect("Failed to generate mnemonic");
+    let mnemonic = match source {
+        EntropySource::Csprng => generator.generate(),
+        EntropySource::Os => {
+            let mut entropy = Zeroizing::new(vec![0u8; strength as usize / 8]);
+            OsRng.try_fill_bytes(&mut entropy).expect("OS random number generator failed");
+            generator.from_entrophy(std::mem::take(&mut *entropy))
+        }
+    }
+    .expect("Failed to generate mnemonic");
 
     (SecureSeed::new(&mnemonic), actual_word_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    /// Second characters of real P2PK addresses on `network`
+    fn start_chars_seen(network: NetworkPrefix) -> Vec<char> {
+        generate_addresses(SEED, 40, AddressFormat::default(), ERGO_COIN_TYPE)
+            .iter()
+            .map(|info| {
+                let address = AddressEncoder::unchecked_parse_address_from_str(&info.address).unwrap();
+                AddressEncoder::encode_address_as_string(network, &address).chars().nth(1).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seed_bytes_derive_the_mnemonics_addresses() {
+        let seed = Zeroizing::new(Mnemonic::to_seed(SEED, ""));
+        let addresses = generate_addresses_from_seed(&*seed, 2, AddressFormat::default(), ERGO_COIN_TYPE).unwrap();
+        assert_eq!(addresses[0].address, "9fv2n41gttbUx8oqqhexi68qPfoETFPxnLEEbTfaTk4SmY2knYC");
+        let from_mnemonic = generate_addresses(SEED, 2, AddressFormat::default(), ERGO_COIN_TYPE);
+        assert_eq!(addresses[1].address, from_mnemonic[1].address);
+        assert_eq!(
+            generate_addresses_from_seed(&seed[..32], 1, AddressFormat::default(), ERGO_COIN_TYPE).unwrap_err(),
+            "Seed must be 64 bytes, got 32"
+        );
+    }
+
+    #[test]
+    fn verify_mnemonic_checks_words_and_checksum() {
+        assert!(verify_mnemonic(SEED).is_ok());
+        for word_count in [12, 15, 24] {
+            let (seed, _) = generate_secure_mnemonic(word_count, EntropySource::Csprng);
+            assert!(verify_mnemonic(seed.as_str()).is_ok());
+        }
+        // The last word carries the checksum, so swapping it breaks the phrase
+        assert!(verify_mnemonic(&SEED.replace("about", "abandon")).is_err());
+        assert!(verify_mnemonic(&SEED.replace("about", "notaword")).is_err());
+        assert!(verify_mnemonic("abandon abandon abandon").is_err());
+    }
+
+    #[test]
+    fn p2pk_address_length_is_measured_from_real_addresses() {
+        assert_eq!(p2pk_address_length(), 51);
+        for info in generate_addresses(SEED, 10, AddressFormat::default(), ERGO_COIN_TYPE) {
+            assert_eq!(info.address.len(), p2pk_address_length());
+        }
+    }
+
+    #[test]
+    fn mainnet_p2pk_addresses_start_within_the_set() {
+        let valid = valid_start_chars(NetworkPrefix::Mainnet, AddressTypePrefix::P2Pk).unwrap();
+        assert_eq!(valid, p2pk_start_chars());
+        for c in start_chars_seen(NetworkPrefix::Mainnet) {
+            assert!(valid.contains(&c), "'{}' is not in {:?}", c, valid);
+        }
+        assert!(is_p2pk_start_char('E', false));
+        assert!(!is_p2pk_start_char('E', true));
+        assert!(!is_p2pk_start_char('a', false));
+    }
+
+    #[test]
+    fn testnet_p2pk_addresses_start_within_the_set() {
+        let valid = valid_start_chars(NetworkPrefix::Testnet, AddressTypePrefix::P2Pk).unwrap();
+        for c in start_chars_seen(NetworkPrefix::Testnet) {
+            assert!(valid.contains(&c), "'{}' is not in {:?}", c, valid);
+        }
+    }
+
+    #[test]
+    fn script_addresses_have_no_fixed_start_set() {
+        for network in [NetworkPrefix::Mainnet, NetworkPrefix::Testnet] {
+            assert!(valid_start_chars(network, AddressTypePrefix::Pay2Sh).is_none());
+            assert!(valid_start_chars(network, AddressTypePrefix::Pay2S).is_none());
+        }
+    }
+}
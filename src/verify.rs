@@ -0,0 +1,59 @@
+//! Confirms that an address was derived from a seed phrase, e.g. before funding a vanity address.
+
+use ergo_lib::ergotree_ir::chain::address::{Address, AddressEncoder, NetworkPrefix};
+use ergo_lib::wallet::ext_secret_key::ExtSecretKey;
+use ergo_lib::wallet::mnemonic::Mnemonic;
+use zeroize::Zeroizing;
+use crate::utils::{derivation_path, derivation_path_string, verify_mnemonic};
+
+/// Searches address indices `0..indices` of accounts `0..accounts` of `mnemonic`, under
+/// `coin_type`, for `address`, which may be a mainnet or testnet address (the derived keys are
+/// the same on both). Returns the network of the address and the derivation path it was found
+/// at, if any.
+pub fn find_address(mnemonic: &str, address: &str, indices: u32, accounts: u32, coin_type: u32) -> Result<(NetworkPrefix, Option<String>), String> {
+    verify_mnemonic(mnemonic).map_err(|e| format!("Invalid seed phrase: {}", e))?;
+    let target = AddressEncoder::unchecked_parse_network_address_from_str(address.trim())
+        .map_err(|e| format!("Invalid address '{}': {}", address.trim(), e))?;
+
+    let seed = Zeroizing::new(Mnemonic::to_seed(mnemonic, ""));
+    let master_key = ExtSecretKey::derive_master(*seed)
+        .map_err(|e| format!("Failed to derive master key: {}", e))?;
+    for account in 0..accounts {
+        for index in 0..indices {
+            let derived = master_key
+                .derive(derivation_path(coin_type, account, index)?)
+                .map_err(|e| format!("Failed to derive {}: {}", derivation_path_string(coin_type, account, index), e))?;
+            let public_key = derived.public_key()
+                .map_err(|e| format!("Failed to get public key: {}", e))?;
+            if Address::from(public_key) == target.address() {
+                return Ok((target.network(), Some(derivation_path_string(coin_type, account, index))));
+            }
+        }
+    }
+    Ok((target.network(), None))
+}
+
+/// Reads a seed phrase from stdin and reports whether `address` belongs to it.
+/// Returns whether it was found.
+pub fn run(address: &str, indices: u32, accounts: u32, coin_type: u32) -> Result<bool, String> {
+    println!("Paste the seed phrase and press Enter:");
+    let mut mnemonic = Zeroizing::new(String::new());
+    std::io::stdin().read_line(&mut mnemonic).map_err(|e| e.to_string())?;
+
+    let (network, path) = find_address(mnemonic.trim(), address, indices, accounts, coin_type)?;
+    let network = match network {
+        NetworkPrefix::Mainnet => "mainnet",
+        NetworkPrefix::Testnet => "testnet",
+    };
+    let searched = format!(
+        "address indices 0-{} of account{} 0-{}",
+        indices.saturating_sub(1),
+        if accounts == 1 { "" } else { "s" },
+        accounts.saturating_sub(1)
+    );
+    match &path {
+        Some(path) => println!("FOUND: {} ({}) belongs to this seed at {}", address.trim(), network, path),
+        None => println!("NOT FOUND: {} ({}) is not in {} of this seed", address.trim(), network, searched),
+    }
+    Ok(path.is_some())
+}